@@ -0,0 +1,331 @@
+//! Formatting of swag annotation comments, as `swag fmt` does
+//!
+//! Consecutive annotation lines are split into cells and aligned with tabs the
+//! way Go's `text/tabwriter` aligns them, and annotation names get their
+//! canonical casing. Only the annotation lines of function doc comments, and of
+//! the package doc comment in the general API info file, are rewritten; every
+//! other byte of the file is left as it was.
+
+use std::collections::{BTreeSet, HashMap};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::go_ast::{self, SyntaxError};
+
+// The text of an annotation comment, without its `//` marker
+static ANNOTATION_LINE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*@([\w-]+)((?:\.[\w.-]+)?)(?:\s+(.*?))?\s*$").unwrap());
+
+// Canonical spelling of annotation names, keyed by their lowercase form
+static CANONICAL_NAMES: Lazy<HashMap<String, &'static str>> = Lazy::new(|| {
+    [
+        // General API info
+        "title",
+        "version",
+        "termsOfService",
+        "contact",
+        "license",
+        "host",
+        "BasePath",
+        "schemes",
+        "server",
+        "tag",
+        "securityDefinitions",
+        "securityScheme",
+        "externalDocs",
+        // Operations
+        "Summary",
+        "ID",
+        "Tags",
+        "Accept",
+        "Produce",
+        "Param",
+        "Success",
+        "Failure",
+        "Response",
+        "Header",
+        "Router",
+        "DeprecatedRouter",
+        "Webhook",
+        "Security",
+        "Deprecated",
+        "Callback",
+    ]
+    .into_iter()
+    .map(|name| (name.to_lowercase(), name))
+    .collect()
+});
+
+// Annotations whose casing is left alone: `@description` is written lowercase in
+// general API info and capitalized on operations, and the attribute lines of a
+// security definition (`@in`, `@scope.read`, ...) have no fixed spelling in swag
+const OTHER_NAMES: &[&str] = &[
+    "description",
+    "requestbody",
+    "in",
+    "name",
+    "tokenurl",
+    "authorizationurl",
+    "scope",
+];
+
+// Tab stops and cell padding, matching swag's tabwriter settings
+const TAB_WIDTH: usize = 4;
+const PADDING: usize = 1;
+
+/// Formats the annotation comments of Go source files
+#[derive(Debug, Default)]
+pub struct Formatter;
+
+impl Formatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format the annotation comments of a Go source file. The package doc comment
+    /// is formatted too when the file holds the general API info.
+    pub fn format_source(&self, source: &str, general_info: bool) -> Result<String, SyntaxError> {
+        let file = go_ast::parse_file(source)?;
+
+        // Comments elsewhere, such as inside function bodies, are not annotations
+        let package_doc = file.doc.as_ref().filter(|_| general_info);
+        let doc_comments = file
+            .funcs
+            .iter()
+            .filter_map(|func| func.doc.as_ref())
+            .chain(package_doc)
+            .flat_map(|group| &group.comments);
+
+        // 1-based lines holding nothing but an annotation comment
+        let annotation_lines: BTreeSet<usize> = doc_comments
+            .filter(|comment| comment.standalone && comment.text.starts_with("//"))
+            .filter(|comment| is_annotation(comment.content()))
+            .map(|comment| comment.pos.line)
+            .collect();
+
+        let mut lines: Vec<String> = source.split_inclusive('\n').map(str::to_string).collect();
+
+        // Lines of a block are aligned together
+        let mut blocks: Vec<Vec<usize>> = Vec::new();
+        for line in annotation_lines {
+            match blocks.last_mut() {
+                Some(block) if block.last() == Some(&(line - 1)) => block.push(line),
+                _ => blocks.push(vec![line]),
+            }
+        }
+
+        for block in blocks {
+            let rows: Vec<Vec<String>> = block
+                .iter()
+                .map(|&line| annotation_cells(comment_text(&lines[line - 1])))
+                .collect();
+            for (&line, aligned) in block.iter().zip(align(&rows)) {
+                let original = &lines[line - 1];
+                let indent_len = original.len() - original.trim_start().len();
+                let ending = &original[original.trim_end_matches(['\r', '\n']).len()..];
+                lines[line - 1] = format!("{}{}{}", &original[..indent_len], aligned, ending);
+            }
+        }
+
+        Ok(lines.concat())
+    }
+}
+
+// Whether a comment is an annotation swag knows, rather than prose like `@see`
+fn is_annotation(text: &str) -> bool {
+    ANNOTATION_LINE_REGEX.captures(text).is_some_and(|captures| {
+        let name = captures[1].to_lowercase();
+        name.starts_with("x-")
+            || CANONICAL_NAMES.contains_key(&name)
+            || OTHER_NAMES.contains(&name.as_str())
+    })
+}
+
+// The text of a `//` comment line after its marker
+fn comment_text(line: &str) -> &str {
+    line.trim().strip_prefix("//").unwrap_or("")
+}
+
+// Split an annotation into cells: the comment marker, the annotation name, the
+// leading fields of annotations with a columnar syntax and the trailing text
+fn annotation_cells(text: &str) -> Vec<String> {
+    let captures = ANNOTATION_LINE_REGEX.captures(text).unwrap();
+    let name = &captures[1];
+    let canonical = CANONICAL_NAMES
+        .get(&name.to_lowercase())
+        .copied()
+        .unwrap_or(name);
+    let mut cells = vec!["//".to_string(), format!("@{}{}", canonical, &captures[2])];
+
+    let value = captures.get(3).map_or("", |m| m.as_str());
+    if value.is_empty() {
+        return cells;
+    }
+
+    // Only plain annotations have fields; `@Callback.success ...` and the like are free text
+    let columns = if captures[2].is_empty() {
+        match canonical {
+            "Param" => 5,
+            "Success" | "Failure" | "Response" | "Header" | "Callback" => 3,
+            "Router" | "DeprecatedRouter" | "Webhook" => 1,
+            _ => 0,
+        }
+    } else {
+        0
+    };
+    // Free text is the last cell of its row, so it keeps its own tabs and spacing
+    if columns == 0 {
+        cells.push(value.to_string());
+        return cells;
+    }
+
+    let mut rest = value;
+    for _ in 0..columns {
+        let Some((field, remainder)) = split_field(rest) else {
+            break;
+        };
+        cells.push(field.to_string());
+        rest = remainder.trim_start();
+    }
+    if !rest.is_empty() {
+        cells.push(rest.to_string());
+    }
+    cells
+}
+
+// Split off the first field at whitespace outside quotes and brackets, so
+// `"user id"`, `Enums(a, b)` and `Response{data=User, meta=Meta}` stay whole
+fn split_field(value: &str) -> Option<(&str, &str)> {
+    if value.is_empty() {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '(' | '[' | '{' if !in_quotes => depth += 1,
+            ')' | ']' | '}' if !in_quotes => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && !in_quotes && depth == 0 => {
+                return Some((&value[..index], &value[index..]));
+            }
+            _ => {}
+        }
+    }
+    Some((value, ""))
+}
+
+// Join rows of cells with tab padding. As in Go's text/tabwriter, every cell but
+// the last of a row is aligned with the cells of the same column on adjacent rows
+// that have one, and cells are padded to the next tab stop.
+fn align(rows: &[Vec<String>]) -> Vec<String> {
+    let mut output = vec![String::new(); rows.len()];
+    format_block(rows, 0, rows.len(), &mut Vec::new(), &mut output);
+    output
+}
+
+fn format_block(
+    rows: &[Vec<String>],
+    mut line0: usize,
+    line1: usize,
+    widths: &mut Vec<usize>,
+    output: &mut [String],
+) {
+    let column = widths.len();
+    let mut this = line0;
+    while this < line1 {
+        if column + 1 >= rows[this].len() {
+            this += 1;
+            continue;
+        }
+
+        // A column block starts here: write the rows before it, then size the column
+        write_rows(rows, line0, this, widths, output);
+        line0 = this;
+        let mut width = 0;
+        while this < line1 && column + 1 < rows[this].len() {
+            width = width.max(rows[this][column].chars().count() + PADDING);
+            this += 1;
+        }
+
+        widths.push(width);
+        format_block(rows, line0, this, widths, output);
+        widths.pop();
+        line0 = this;
+    }
+    write_rows(rows, line0, line1, widths, output);
+}
+
+fn write_rows(
+    rows: &[Vec<String>],
+    line0: usize,
+    line1: usize,
+    widths: &[usize],
+    output: &mut [String],
+) {
+    for (row, out) in rows[line0..line1].iter().zip(&mut output[line0..line1]) {
+        for (j, cell) in row.iter().enumerate() {
+            out.push_str(cell);
+            if let Some(&width) = widths.get(j) {
+                let cell_width = width.div_ceil(TAB_WIDTH) * TAB_WIDTH;
+                let padding = cell_width - cell.chars().count();
+                out.push_str(&"\t".repeat(padding.div_ceil(TAB_WIDTH)));
+            }
+        }
+    }
+}
+
+/// A unified diff between the original and formatted text of a file. Formatting
+/// rewrites lines in place, so the two texts always have the same lines.
+pub fn unified_diff(path: &str, original: &str, formatted: &str) -> String {
+    const CONTEXT: usize = 3;
+    let old: Vec<&str> = original.lines().collect();
+    let new: Vec<&str> = formatted.lines().collect();
+    let changed: Vec<usize> = (0..old.len().min(new.len()))
+        .filter(|&i| old[i] != new[i])
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    let path = path.trim_start_matches("./");
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let mut index = 0;
+    while index < changed.len() {
+        // Changes closer than twice the context share a hunk
+        let mut end = index;
+        while end + 1 < changed.len() && changed[end + 1] - changed[end] <= 2 * CONTEXT {
+            end += 1;
+        }
+        let start_line = changed[index].saturating_sub(CONTEXT);
+        let end_line = (changed[end] + CONTEXT + 1).min(old.len());
+        let count = end_line - start_line;
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start_line + 1,
+            count,
+            start_line + 1,
+            count
+        ));
+        // Runs of changed lines are written as removals followed by additions
+        let mut line = start_line;
+        while line < end_line {
+            if old[line] == new[line] {
+                diff.push_str(&format!(" {}\n", old[line]));
+                line += 1;
+                continue;
+            }
+            let run_end = (line..end_line).find(|&i| old[i] == new[i]).unwrap_or(end_line);
+            for removed in &old[line..run_end] {
+                diff.push_str(&format!("-{}\n", removed));
+            }
+            for added in &new[line..run_end] {
+                diff.push_str(&format!("+{}\n", added));
+            }
+            line = run_end;
+        }
+        index = end + 1;
+    }
+    diff
+}
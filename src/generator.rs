@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::{
     collections::{HashMap, HashSet},
     fs::{self, File},
@@ -69,7 +69,10 @@ impl Generator {
         ))?;
 
         // Build the OpenAPI document
-        let openapi = self.build_openapi_doc();
+        let (openapi, placeholders) = self.build_openapi_doc();
+        for name in placeholders {
+            warn!("Schema {} is referenced but not defined; using an empty placeholder", name);
+        }
 
         // Generate the specified output types
         for output_type in output_types {
@@ -89,13 +92,15 @@ impl Generator {
         Ok(())
     }
 
-    /// Build a complete OpenAPI 3.1.1 document
-    fn build_openapi_doc(&self) -> OpenAPI {
+    /// Build a complete OpenAPI 3.1.1 document, with the names of the schemas
+    /// that had to be filled with placeholders
+    fn build_openapi_doc(&self) -> (OpenAPI, Vec<String>) {
         let mut openapi = OpenAPI {
             openapi: self.openapi_version.clone(),
             info: self.api_info.info.clone(),
             paths: HashMap::new(),
             components: None, // Will be set later
+            extensions: self.api_info.extensions.clone(),
             ..Default::default()
         };
 
@@ -164,14 +169,22 @@ impl Generator {
             let path = operation.path.clone();
             let method = operation.method.clone();
 
-            let path_item = openapi.paths.entry(path).or_default();
-
             // Convert legacy consumes/produces to requestBody/responses content
             let mut op = operation.operation.clone();
 
             // Fix schema references in the operation
             self.fix_operation_references(&mut op);
 
+            if operation.webhook {
+                let webhooks = openapi.webhooks.get_or_insert_with(HashMap::new);
+                if !webhooks.entry(path).or_default().set_operation(&method, op) {
+                    debug!("Unknown HTTP method: {}", method);
+                }
+                continue;
+            }
+
+            let path_item = openapi.paths.entry(path).or_default();
+
             // Add path parameters if needed
             path_item.parameters = operation
                 .operation
@@ -194,7 +207,15 @@ impl Generator {
             }
         }
 
-        openapi
+        // Ensure all referenced schemas are present in the document
+        let placeholders = self.ensure_referenced_schemas_exist(&mut openapi);
+
+        // OpenAPI 3.0 readers do not understand the 3.1 constructs used above
+        if self.openapi_version.starts_with("3.0") {
+            downgrade_to_oas30(&mut openapi);
+        }
+
+        (openapi, placeholders)
     }
 
     /// Fix references in schemas to use the correct format for OpenAPI 3.1.1
@@ -1071,6 +1092,7 @@ func ServeDirectoryListing(w http.ResponseWriter, r *http.Request, dir string) {
             info: self.api_info.info.clone(),
             paths: HashMap::new(),
             tags: self.api_info.tags.clone(),
+            extensions: self.api_info.extensions.clone(),
             ..Default::default()
         };
 
@@ -1094,6 +1116,16 @@ func ServeDirectoryListing(w http.ResponseWriter, r *http.Request, dir string) {
             let path = operation.path.clone();
             let method = operation.method.clone();
 
+            if operation.webhook {
+                openapi
+                    .webhooks
+                    .get_or_insert_with(HashMap::new)
+                    .entry(path)
+                    .or_default()
+                    .set_operation(&method, operation.operation.clone());
+                continue;
+            }
+
             // Ensure the path exists
             if !openapi.paths.contains_key(&path) {
                 openapi.paths.insert(path.clone(), PathItem::default());
@@ -1160,80 +1192,93 @@ func ServeDirectoryListing(w http.ResponseWriter, r *http.Request, dir string) {
         openapi
     }
 
-    // Add method to ensure all referenced schemas exist in the components
-    #[allow(dead_code)]
-    fn ensure_referenced_schemas_exist(&self, openapi: &mut OpenAPI) {
-        // Create a function to find schema references in an object
+    // Add empty placeholders for referenced schemas that are not defined, and
+    // return their names
+    fn ensure_referenced_schemas_exist(&self, openapi: &mut OpenAPI) -> Vec<String> {
         let mut references = HashSet::new();
 
-        // Check paths
-        for path_item in openapi.paths.values() {
-            // Check each operation
-            for operation in [
-                &path_item.get,
-                &path_item.post,
-                &path_item.put,
-                &path_item.delete,
-                &path_item.options,
-                &path_item.head,
-                &path_item.patch,
-                &path_item.trace,
-            ]
-            .iter()
-            .filter_map(|&op| op.as_ref())
-            {
-                // Check request body
-                if let Some(request_body) = &operation.requestBody {
-                    for media_type in request_body.content.values() {
-                        if let Some(schema) = &media_type.schema {
-                            self.collect_references(schema, &mut references);
-                        }
-                    }
-                }
+        // Check operations of paths and webhooks
+        let path_items = openapi
+            .paths
+            .values()
+            .chain(openapi.webhooks.iter().flat_map(|webhooks| webhooks.values()));
+        for path_item in path_items {
+            for operation in path_item.operations() {
+                self.collect_operation_references(operation, &mut references);
+            }
+        }
 
-                // Check parameters
-                for param in &operation.parameters {
-                    if let Some(schema) = &param.schema {
-                        self.collect_references(schema, &mut references);
-                    }
-                }
+        // Schemas refer to each other too
+        let components = openapi.components.get_or_insert_with(Components::default);
+        for schema in components.schemas.values() {
+            self.collect_references(schema, &mut references);
+        }
 
-                // Check responses
-                for response in operation.responses.values() {
-                    for media_type in response.content.values() {
-                        if let Some(schema) = &media_type.schema {
-                            self.collect_references(schema, &mut references);
-                        }
-                    }
+        // Make sure all referenced schemas exist in components
+        let mut placeholders = Vec::new();
+        for reference in references {
+            // Extract the model name from the reference
+            let Some(model_name) = reference.strip_prefix("#/components/schemas/") else {
+                continue;
+            };
+            if components.schemas.contains_key(model_name) {
+                continue;
+            }
+
+            // If the schema doesn't exist but we have it in our schemas map
+            if let Some(schema) = self.schemas.get(model_name) {
+                components
+                    .schemas
+                    .insert(model_name.to_string(), schema.clone());
+            } else {
+                // If we don't have it, create a placeholder
+                components
+                    .schemas
+                    .insert(model_name.to_string(), Schema::default());
+                placeholders.push(model_name.to_string());
+            }
+        }
+
+        placeholders.sort();
+        placeholders
+    }
+
+    // Collect schema references from an operation and its callbacks
+    fn collect_operation_references(&self, operation: &Operation, references: &mut HashSet<String>) {
+        // Check request body
+        if let Some(request_body) = &operation.requestBody {
+            for media_type in request_body.content.values() {
+                if let Some(schema) = &media_type.schema {
+                    self.collect_references(schema, references);
                 }
             }
         }
 
-        // Make sure all referenced schemas exist in components
-        if let Some(components) = &mut openapi.components {
-            for reference in references {
-                // Extract the model name from the reference
-                if let Some(model_name) = reference.strip_prefix("#/components/schemas/") {
-                    if !components.schemas.contains_key(model_name) {
-                        // If the schema doesn't exist but we have it in our schemas map
-                        if let Some(schema) = self.schemas.get(model_name) {
-                            components
-                                .schemas
-                                .insert(model_name.to_string(), schema.clone());
-                        } else {
-                            // If we don't have it, create a placeholder
-                            components
-                                .schemas
-                                .insert(model_name.to_string(), Schema::default());
-                        }
-                    }
+        // Check parameters
+        for param in &operation.parameters {
+            if let Some(schema) = &param.schema {
+                self.collect_references(schema, references);
+            }
+        }
+
+        // Check responses
+        for response in operation.responses.values() {
+            for media_type in response.content.values() {
+                if let Some(schema) = &media_type.schema {
+                    self.collect_references(schema, references);
                 }
             }
         }
+
+        // Check callback operations
+        for path_item in operation.callbacks.values().flat_map(|callback| callback.values()) {
+            for callback_operation in path_item.operations() {
+                self.collect_operation_references(callback_operation, references);
+            }
+        }
     }
 
     // Helper to collect references from a schema
-    #[allow(dead_code)]
     fn collect_references(&self, schema: &Schema, references: &mut HashSet<String>) {
         if let Some(ref_) = &schema.ref_ {
             references.insert(ref_.clone());
@@ -1268,6 +1313,12 @@ func ServeDirectoryListing(w http.ResponseWriter, r *http.Request, dir string) {
         for prop in schema.properties.values() {
             self.collect_references(prop, references);
         }
+
+        if let Some(additional) = &schema.additionalProperties {
+            if let Ok(additional) = serde_json::from_value::<Schema>(additional.clone()) {
+                self.collect_references(&additional, references);
+            }
+        }
     }
 
     #[allow(dead_code)]
@@ -1276,3 +1327,160 @@ func ServeDirectoryListing(w http.ResponseWriter, r *http.Request, dir string) {
         self.convert_to_openapi()
     }
 }
+
+/// Rewrite a document using OpenAPI 3.1 constructs with OpenAPI 3.0 semantics;
+/// what 3.0 cannot express is dropped
+fn downgrade_to_oas30(openapi: &mut OpenAPI) {
+    openapi.jsonSchemaDialect = None;
+    if let Some(webhooks) = openapi.webhooks.take() {
+        warn!("OpenAPI 3.0 has no webhooks; dropping {} webhook(s)", webhooks.len());
+    }
+    openapi.info.summary = None;
+    if let Some(license) = &mut openapi.info.license {
+        license.identifier = None;
+    }
+
+    let mut visit = downgrade_schema;
+    for path_item in openapi.paths.values_mut() {
+        for parameter in &mut path_item.parameters {
+            if let Some(schema) = &mut parameter.schema {
+                schema.walk_mut(&mut visit);
+            }
+        }
+        for operation in path_item.operations_mut() {
+            operation.walk_schemas_mut(&mut visit);
+        }
+    }
+
+    if let Some(components) = &mut openapi.components {
+        components.pathItems.clear();
+        for schema in components.schemas.values_mut() {
+            schema.walk_mut(&mut visit);
+        }
+    }
+}
+
+// Whether a schema serializes to anything but `{}`
+fn has_keywords(schema: &Schema) -> bool {
+    serde_json::to_value(schema).is_ok_and(|value| value != serde_json::json!({}))
+}
+
+// Rewrite the JSON Schema 2020-12 keywords of one schema for OpenAPI 3.0
+fn downgrade_schema(schema: &mut Schema) {
+    // `anyOf: [X, {type: "null"}]` becomes X with `nullable: true`
+    if let Some(alternatives) = &mut schema.anyOf {
+        let is_null = |alternative: &Schema| {
+            alternative.type_.as_ref().is_some_and(|t| t == "null")
+                && !has_keywords(&Schema {
+                    type_: None,
+                    ..alternative.clone()
+                })
+        };
+        if alternatives.iter().any(is_null) {
+            alternatives.retain(|alternative| !is_null(alternative));
+            schema.nullable = Some(true);
+            if alternatives.len() == 1 {
+                let alternative = alternatives.pop();
+                schema.anyOf = None;
+                schema.allOf.get_or_insert_with(Vec::new).extend(alternative);
+            }
+        }
+    }
+
+    // `type: [string, "null"]` becomes `type: string` with `nullable: true`
+    if let Some(serde_json::Value::Array(types)) = &schema.type_ {
+        let mut types = types.clone();
+        if types.iter().any(|t| t == "null") {
+            schema.nullable = Some(true);
+            types.retain(|t| t != "null");
+        }
+        schema.type_ = if types.len() > 1 {
+            // Several types are alternatives
+            let alternatives = types
+                .into_iter()
+                .map(|t| Schema {
+                    type_: Some(t),
+                    ..Default::default()
+                })
+                .collect();
+            schema.allOf.get_or_insert_with(Vec::new).push(Schema {
+                anyOf: Some(alternatives),
+                ..Default::default()
+            });
+            None
+        } else {
+            types.pop()
+        };
+    }
+
+    // Numeric exclusive bounds become flags on minimum and maximum
+    if let Some(bound) = schema.exclusiveMinimum.as_ref().and_then(|b| b.as_f64()) {
+        if schema.minimum.is_some_and(|minimum| minimum > bound) {
+            // The inclusive minimum is the tighter bound
+            schema.exclusiveMinimum = None;
+        } else {
+            schema.minimum = Some(bound);
+            schema.exclusiveMinimum = Some(serde_json::Value::Bool(true));
+        }
+    }
+    if let Some(bound) = schema.exclusiveMaximum.as_ref().and_then(|b| b.as_f64()) {
+        if schema.maximum.is_some_and(|maximum| maximum < bound) {
+            schema.exclusiveMaximum = None;
+        } else {
+            schema.maximum = Some(bound);
+            schema.exclusiveMaximum = Some(serde_json::Value::Bool(true));
+        }
+    }
+
+    if let Some(value) = schema.const_.take() {
+        schema.enum_values = Some(vec![value]);
+    }
+
+    // Tuples cannot be expressed, but every item is still one of the tuple's schemas
+    if let Some(prefix_items) = schema.prefixItems.take() {
+        if schema.items.is_none() && !prefix_items.is_empty() {
+            schema.items = Some(Box::new(Schema {
+                anyOf: Some(prefix_items),
+                ..Default::default()
+            }));
+        }
+    }
+
+    // OpenAPI 3.0 ignores the siblings of a $ref, so the reference moves into allOf
+    if let Some(reference) = schema.ref_.take() {
+        if !has_keywords(schema) {
+            schema.ref_ = Some(reference);
+        } else {
+            schema.allOf.get_or_insert_with(Vec::new).insert(
+                0,
+                Schema {
+                    ref_: Some(reference),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    // Keywords OpenAPI 3.0 does not have
+    schema.schema_ = None;
+    schema.id_ = None;
+    schema.anchor_ = None;
+    schema.dynamic_ref_ = None;
+    schema.dynamic_anchor_ = None;
+    schema.vocabulary_.clear();
+    schema.comment_ = None;
+    schema.defs_.clear();
+    schema.contains = None;
+    schema.minContains = None;
+    schema.maxContains = None;
+    schema.patternProperties = None;
+    schema.propertyNames = None;
+    schema.unevaluatedItems = None;
+    schema.unevaluatedProperties = None;
+    schema.if_ = None;
+    schema.then = None;
+    schema.else_ = None;
+    schema.contentEncoding = None;
+    schema.contentMediaType = None;
+    schema.contentSchema = None;
+}
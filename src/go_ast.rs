@@ -0,0 +1,1253 @@
+//! A small Go lexer and declaration parser
+//!
+//! Only what documentation generation needs is modelled: the package clause,
+//! imports, type, const and func declarations, struct fields with their tags,
+//! and the comments attached to all of them. Function bodies and var
+//! declarations are skipped as balanced token runs.
+
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped, tag, take_until, take_while},
+    character::complete::{anychar, char, none_of, one_of, satisfy},
+    combinator::{opt, recognize},
+    multi::many0,
+    sequence::{delimited, pair},
+    IResult,
+};
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+/// 1-based line and column of a token or comment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("{}:{}: {message}", .pos.line, .pos.column)]
+pub struct SyntaxError {
+    pub message: String,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Int,
+    Float,
+    Imaginary,
+    Char,
+    String,
+    RawString,
+    Operator,
+    Semicolon,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub pos: Position,
+    pub offset: usize,
+}
+
+/// A `//` or `/* */` comment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub pos: Position,
+    pub end_line: usize,
+    /// Whether the comment is the first thing on its line (not trailing code)
+    pub standalone: bool,
+}
+
+impl Comment {
+    /// The comment text without its `//` or `/* */` markers
+    pub fn content(&self) -> &str {
+        if let Some(line) = self.text.strip_prefix("//") {
+            line
+        } else {
+            self.text
+                .strip_prefix("/*")
+                .and_then(|text| text.strip_suffix("*/"))
+                .unwrap_or(&self.text)
+        }
+    }
+}
+
+/// Comments on consecutive lines, attached to the declaration that follows
+/// them or trailing the line they end
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommentGroup {
+    pub comments: Vec<Comment>,
+}
+
+impl CommentGroup {
+    /// Text of the group with comment markers removed, like go/ast's `CommentGroup.Text`
+    pub fn text(&self) -> String {
+        let mut lines: Vec<&str> = Vec::new();
+        for comment in &self.comments {
+            // Tool directives such as //go:generate are not documentation
+            if comment.text.starts_with("//go:") {
+                continue;
+            }
+            // An empty `//` line still separates paragraphs
+            let content = comment.content();
+            let content_lines = if content.is_empty() {
+                vec![""]
+            } else {
+                content.lines().collect()
+            };
+            for line in content_lines {
+                let line = line.strip_prefix(' ').unwrap_or(line);
+                lines.push(line.trim_end());
+            }
+        }
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        while lines.first().is_some_and(|line| line.is_empty()) {
+            lines.remove(0);
+        }
+        lines.join("\n")
+    }
+
+    fn start_line(&self) -> usize {
+        self.comments.first().map_or(0, |c| c.pos.line)
+    }
+
+    fn end_line(&self) -> usize {
+        self.comments.last().map_or(0, |c| c.end_line)
+    }
+}
+
+/// A parsed Go source file
+#[derive(Debug, Clone, Default)]
+pub struct GoFile {
+    pub package: String,
+    /// The comment group just above the package clause
+    pub doc: Option<CommentGroup>,
+    pub imports: Vec<ImportSpec>,
+    pub types: Vec<TypeSpec>,
+    pub consts: Vec<ConstDecl>,
+    pub funcs: Vec<FuncDecl>,
+    pub comments: Vec<Comment>,
+    /// Declarations that could not be parsed and were skipped
+    pub errors: Vec<SyntaxError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportSpec {
+    pub name: Option<String>,
+    pub path: String,
+    pub pos: Position,
+}
+
+impl ImportSpec {
+    /// Name the package is referred to by: the alias, or the last path element
+    pub fn package_name(&self) -> &str {
+        match &self.name {
+            Some(name) => name,
+            None => self.path.rsplit('/').next().unwrap_or(&self.path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeSpec {
+    pub name: String,
+    pub type_params: Vec<TypeParam>,
+    pub is_alias: bool,
+    pub ty: TypeExpr,
+    pub doc: Option<CommentGroup>,
+    pub comment: Option<CommentGroup>,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParam {
+    pub name: String,
+    pub constraint: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExpr {
+    Named {
+        package: Option<String>,
+        name: String,
+        type_args: Vec<TypeExpr>,
+    },
+    Pointer(Box<TypeExpr>),
+    Slice(Box<TypeExpr>),
+    Array {
+        len: String,
+        elem: Box<TypeExpr>,
+    },
+    Map {
+        key: Box<TypeExpr>,
+        value: Box<TypeExpr>,
+    },
+    Chan(Box<TypeExpr>),
+    Func,
+    Struct(Vec<Field>),
+    Interface,
+}
+
+impl TypeExpr {
+    /// Replace type parameters by their arguments
+    pub fn substitute(&self, params: &HashMap<String, TypeExpr>) -> TypeExpr {
+        match self {
+            TypeExpr::Named {
+                package: None,
+                name,
+                type_args,
+            } if type_args.is_empty() && params.contains_key(name) => params[name].clone(),
+            TypeExpr::Named {
+                package,
+                name,
+                type_args,
+            } => TypeExpr::Named {
+                package: package.clone(),
+                name: name.clone(),
+                type_args: type_args.iter().map(|arg| arg.substitute(params)).collect(),
+            },
+            TypeExpr::Pointer(inner) => TypeExpr::Pointer(Box::new(inner.substitute(params))),
+            TypeExpr::Slice(inner) => TypeExpr::Slice(Box::new(inner.substitute(params))),
+            TypeExpr::Array { len, elem } => TypeExpr::Array {
+                len: len.clone(),
+                elem: Box::new(elem.substitute(params)),
+            },
+            TypeExpr::Map { key, value } => TypeExpr::Map {
+                key: Box::new(key.substitute(params)),
+                value: Box::new(value.substitute(params)),
+            },
+            TypeExpr::Chan(inner) => TypeExpr::Chan(Box::new(inner.substitute(params))),
+            TypeExpr::Struct(fields) => TypeExpr::Struct(
+                fields
+                    .iter()
+                    .map(|field| Field {
+                        ty: field.ty.substitute(params),
+                        ..field.clone()
+                    })
+                    .collect(),
+            ),
+            TypeExpr::Func | TypeExpr::Interface => self.clone(),
+        }
+    }
+
+    /// Whether an anonymous struct appears anywhere in the type
+    pub fn has_inline_struct(&self) -> bool {
+        match self {
+            TypeExpr::Struct(_) => true,
+            TypeExpr::Pointer(inner) | TypeExpr::Slice(inner) | TypeExpr::Chan(inner) => {
+                inner.has_inline_struct()
+            }
+            TypeExpr::Array { elem, .. } => elem.has_inline_struct(),
+            TypeExpr::Map { key, value } => key.has_inline_struct() || value.has_inline_struct(),
+            TypeExpr::Named { type_args, .. } => type_args.iter().any(TypeExpr::has_inline_struct),
+            TypeExpr::Func | TypeExpr::Interface => false,
+        }
+    }
+}
+
+/// Renders the type the way it is written in Go, e.g. `map[string][]*models.User`
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeExpr::Named {
+                package,
+                name,
+                type_args,
+            } => {
+                if let Some(package) = package {
+                    write!(f, "{}.", package)?;
+                }
+                write!(f, "{}", name)?;
+                if !type_args.is_empty() {
+                    let args: Vec<String> = type_args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "[{}]", args.join(","))?;
+                }
+                Ok(())
+            }
+            TypeExpr::Pointer(inner) => write!(f, "*{}", inner),
+            TypeExpr::Slice(inner) => write!(f, "[]{}", inner),
+            TypeExpr::Array { len, elem } => write!(f, "[{}]{}", len, elem),
+            TypeExpr::Map { key, value } => write!(f, "map[{}]{}", key, value),
+            TypeExpr::Chan(inner) => write!(f, "chan {}", inner),
+            TypeExpr::Func => write!(f, "func()"),
+            TypeExpr::Struct(_) => write!(f, "struct{{}}"),
+            TypeExpr::Interface => write!(f, "interface{{}}"),
+        }
+    }
+}
+
+/// A struct field; embedded fields have no names
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub names: Vec<String>,
+    pub ty: TypeExpr,
+    /// The tag with its quotes removed
+    pub tag: Option<String>,
+    pub doc: Option<CommentGroup>,
+    pub comment: Option<CommentGroup>,
+    pub pos: Position,
+}
+
+impl Field {
+    pub fn is_embedded(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A `const` declaration; the index of a spec is its `iota`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConstDecl {
+    pub specs: Vec<ConstSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstSpec {
+    pub names: Vec<String>,
+    pub ty: Option<TypeExpr>,
+    /// Source text of each value expression
+    pub values: Vec<String>,
+    pub doc: Option<CommentGroup>,
+    pub comment: Option<CommentGroup>,
+    pub pos: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuncDecl {
+    pub name: String,
+    pub receiver: Option<TypeExpr>,
+    pub doc: Option<CommentGroup>,
+    pub pos: Position,
+}
+
+/// Parse a Go source file. Declarations that fail to parse are skipped and
+/// reported in `GoFile::errors`; only lexical errors fail the whole file.
+pub fn parse_file(source: &str) -> Result<GoFile, SyntaxError> {
+    let (tokens, comments) = lex(source)?;
+    let mut parser = Parser::new(source, tokens, &comments);
+    let mut file = parser.parse_file();
+    file.comments = comments;
+    Ok(file)
+}
+
+/// Parse a type expression such as `[]models.User` or `Page[models.User]`
+pub fn parse_type_expr(source: &str) -> Result<TypeExpr, SyntaxError> {
+    let (tokens, comments) = lex(source)?;
+    let mut parser = Parser::new(source, tokens, &comments);
+    let ty = parser.parse_type()?;
+    parser.skip_semicolons();
+    if !parser.at_end() {
+        return Err(parser.error("unexpected token after type"));
+    }
+    Ok(ty)
+}
+
+fn line_comment(input: &str) -> IResult<&str, &str> {
+    recognize(pair(tag("//"), take_while(|c| c != '\n')))(input)
+}
+
+fn block_comment(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(tag("/*"), take_until("*/"), tag("*/")))(input)
+}
+
+fn raw_string(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(char('`'), take_while(|c| c != '`'), char('`')))(input)
+}
+
+fn interpreted_string(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(
+        char('"'),
+        opt(escaped(none_of("\\\"\n"), '\\', anychar)),
+        char('"'),
+    ))(input)
+}
+
+fn rune_literal(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(
+        char('\''),
+        escaped(none_of("\\'\n"), '\\', anychar),
+        char('\''),
+    ))(input)
+}
+
+fn number(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        alt((
+            recognize(satisfy(|c| c.is_ascii_digit())),
+            recognize(pair(char('.'), satisfy(|c| c.is_ascii_digit()))),
+        )),
+        many0(alt((
+            recognize(pair(one_of("eEpP"), one_of("+-"))),
+            recognize(satisfy(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')),
+        ))),
+    ))(input)
+}
+
+fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_alphabetic() || c == '_'),
+        take_while(|c: char| c.is_alphanumeric() || c == '_'),
+    ))(input)
+}
+
+fn operator(input: &str) -> IResult<&str, &str> {
+    alt((
+        alt((
+            tag("<<="),
+            tag(">>="),
+            tag("&^="),
+            tag("..."),
+            tag("&&"),
+            tag("||"),
+            tag("<-"),
+            tag("++"),
+            tag("--"),
+            tag("=="),
+            tag("!="),
+            tag("<="),
+            tag(">="),
+            tag(":="),
+            tag("+="),
+            tag("-="),
+            tag("*="),
+            tag("/="),
+            tag("%="),
+            tag("&="),
+            tag("|="),
+        )),
+        alt((tag("^="), tag("<<"), tag(">>"), tag("&^"))),
+        recognize(one_of("+-*/%&|^<>=!()[]{},;.:~")),
+    ))(input)
+}
+
+fn token(input: &str) -> IResult<&str, (TokenKind, &str)> {
+    if let Ok((rest, text)) = raw_string(input) {
+        return Ok((rest, (TokenKind::RawString, text)));
+    }
+    if let Ok((rest, text)) = interpreted_string(input) {
+        return Ok((rest, (TokenKind::String, text)));
+    }
+    if let Ok((rest, text)) = rune_literal(input) {
+        return Ok((rest, (TokenKind::Char, text)));
+    }
+    if let Ok((rest, text)) = number(input) {
+        let is_hex = text.starts_with("0x") || text.starts_with("0X");
+        let kind = if text.ends_with('i') {
+            TokenKind::Imaginary
+        } else if text.contains('.') || (!is_hex && text.contains(['e', 'E'])) || (is_hex && text.contains(['p', 'P'])) {
+            TokenKind::Float
+        } else {
+            TokenKind::Int
+        };
+        return Ok((rest, (kind, text)));
+    }
+    if let Ok((rest, text)) = identifier(input) {
+        return Ok((rest, (TokenKind::Ident, text)));
+    }
+
+    let (rest, text) = operator(input)?;
+    let kind = if text == ";" {
+        TokenKind::Semicolon
+    } else {
+        TokenKind::Operator
+    };
+    Ok((rest, (kind, text)))
+}
+
+/// Turns source offsets into line/column positions
+struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let column = self.source[self.line_starts[line]..offset].chars().count() + 1;
+        Position {
+            line: line + 1,
+            column,
+        }
+    }
+}
+
+/// Split source into tokens and comments, inserting semicolons at line ends
+/// following the Go specification
+fn lex(source: &str) -> Result<(Vec<Token>, Vec<Comment>), SyntaxError> {
+    let index = LineIndex::new(source);
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut comments = Vec::new();
+    let mut rest = source;
+    let mut line_has_token = false;
+
+    // A newline ends a statement after an identifier, literal or closing bracket
+    let insert_semicolon = |tokens: &mut Vec<Token>, offset: usize| {
+        let needs_semicolon = tokens.last().is_some_and(|last| match last.kind {
+            TokenKind::Semicolon => false,
+            TokenKind::Operator => matches!(last.text.as_str(), ")" | "]" | "}" | "++" | "--"),
+            _ => true,
+        });
+        if needs_semicolon {
+            tokens.push(Token {
+                kind: TokenKind::Semicolon,
+                text: "\n".to_string(),
+                pos: index.position(offset),
+                offset,
+            });
+        }
+    };
+
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', '\r']);
+        let offset = source.len() - rest.len();
+
+        if rest.is_empty() {
+            insert_semicolon(&mut tokens, offset);
+            break;
+        }
+
+        if rest.starts_with('\n') {
+            insert_semicolon(&mut tokens, offset);
+            line_has_token = false;
+            rest = &rest[1..];
+            continue;
+        }
+
+        if let Ok((remaining, text)) = alt((line_comment, block_comment))(rest) {
+            let pos = index.position(offset);
+            comments.push(Comment {
+                text: text.to_string(),
+                pos,
+                end_line: pos.line + text.matches('\n').count(),
+                standalone: !line_has_token,
+            });
+            // A comment spanning lines acts like a newline
+            if text.contains('\n') {
+                insert_semicolon(&mut tokens, offset);
+                line_has_token = false;
+            }
+            rest = remaining;
+            continue;
+        }
+
+        match token(rest) {
+            Ok((remaining, (kind, text))) => {
+                tokens.push(Token {
+                    kind,
+                    text: text.to_string(),
+                    pos: index.position(offset),
+                    offset,
+                });
+                line_has_token = true;
+                rest = remaining;
+            }
+            Err(_) => {
+                return Err(SyntaxError {
+                    message: format!(
+                        "unexpected character {:?}",
+                        rest.chars().next().unwrap_or_default()
+                    ),
+                    pos: index.position(offset),
+                })
+            }
+        }
+    }
+
+    Ok((tokens, comments))
+}
+
+/// Remove the quotes of a string literal
+fn unquote(text: &str) -> String {
+    if let Some(raw) = text.strip_prefix('`').and_then(|t| t.strip_suffix('`')) {
+        return raw.to_string();
+    }
+    serde_json::from_str::<String>(text).unwrap_or_else(|_| text.trim_matches('"').to_string())
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+    doc_groups: Vec<CommentGroup>,
+    trailing: Vec<Comment>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, tokens: Vec<Token>, comments: &[Comment]) -> Self {
+        // Standalone comments on consecutive lines form doc groups
+        let mut doc_groups: Vec<CommentGroup> = Vec::new();
+        for comment in comments.iter().filter(|c| c.standalone) {
+            match doc_groups.last_mut() {
+                Some(group) if comment.pos.line <= group.end_line() + 1 => {
+                    group.comments.push(comment.clone())
+                }
+                _ => doc_groups.push(CommentGroup {
+                    comments: vec![comment.clone()],
+                }),
+            }
+        }
+
+        Self {
+            source,
+            tokens,
+            index: 0,
+            doc_groups,
+            trailing: comments.iter().filter(|c| !c.standalone).cloned().collect(),
+        }
+    }
+
+    fn parse_file(&mut self) -> GoFile {
+        let mut file = GoFile::default();
+
+        self.skip_semicolons();
+        if self.peek_text() == "package" {
+            file.doc = self.doc_for(self.current_pos().line);
+        }
+        if self.eat("package") {
+            match self.expect_ident() {
+                Ok(name) => file.package = name,
+                Err(error) => file.errors.push(error),
+            }
+        }
+        self.skip_semicolons();
+
+        while !self.at_end() {
+            let start = self.index;
+            let result = match self.peek_text() {
+                "import" => self.parse_import_decl(&mut file.imports),
+                "type" => self.parse_type_decl(&mut file.types),
+                "const" => self.parse_const_decl(&mut file.consts),
+                "func" => self.parse_func_decl().map(|func| file.funcs.push(func)),
+                // var declarations and anything unrecognised
+                _ => {
+                    self.skip_declaration();
+                    Ok(())
+                }
+            };
+
+            if let Err(error) = result {
+                file.errors.push(error);
+                self.index = start;
+                self.skip_declaration();
+            }
+            self.skip_semicolons();
+        }
+
+        file
+    }
+
+    fn parse_import_decl(&mut self, imports: &mut Vec<ImportSpec>) -> Result<(), SyntaxError> {
+        self.expect("import")?;
+        if self.eat("(") {
+            loop {
+                self.skip_semicolons();
+                if self.eat(")") {
+                    break;
+                }
+                imports.push(self.parse_import_spec()?);
+            }
+        } else {
+            imports.push(self.parse_import_spec()?);
+        }
+        Ok(())
+    }
+
+    fn parse_import_spec(&mut self) -> Result<ImportSpec, SyntaxError> {
+        let pos = self.current_pos();
+        let name = match self.peek() {
+            Some(token) if token.kind == TokenKind::Ident || token.text == "." => {
+                let name = token.text.clone();
+                self.index += 1;
+                Some(name)
+            }
+            _ => None,
+        };
+
+        match self.peek() {
+            Some(token) if matches!(token.kind, TokenKind::String | TokenKind::RawString) => {
+                let path = unquote(&token.text);
+                self.index += 1;
+                Ok(ImportSpec { name, path, pos })
+            }
+            _ => Err(self.error("expected import path")),
+        }
+    }
+
+    fn parse_type_decl(&mut self, types: &mut Vec<TypeSpec>) -> Result<(), SyntaxError> {
+        let decl_doc = self.doc_for(self.current_pos().line);
+        self.expect("type")?;
+
+        if self.eat("(") {
+            loop {
+                self.skip_semicolons();
+                if self.eat(")") {
+                    break;
+                }
+                let doc = self.doc_for(self.current_pos().line);
+                types.push(self.parse_type_spec(doc)?);
+            }
+        } else {
+            types.push(self.parse_type_spec(decl_doc)?);
+        }
+        Ok(())
+    }
+
+    fn parse_type_spec(&mut self, doc: Option<CommentGroup>) -> Result<TypeSpec, SyntaxError> {
+        let pos = self.current_pos();
+        let name = self.expect_ident()?;
+
+        // `type Page[T any] struct` declares type parameters, `type Buf [N]byte` an array
+        let type_params = if self.is("[")
+            && self.peek_at(1).is_some_and(|t| t.kind == TokenKind::Ident)
+            && self.peek_at(2).is_some_and(|t| t.text != "]")
+        {
+            self.parse_type_params()?
+        } else {
+            Vec::new()
+        };
+
+        let is_alias = self.eat("=");
+        let ty = self.parse_type()?;
+        let comment = self.trailing_for(self.previous_line());
+
+        Ok(TypeSpec {
+            name,
+            type_params,
+            is_alias,
+            ty,
+            doc,
+            comment,
+            pos,
+        })
+    }
+
+    fn parse_type_params(&mut self) -> Result<Vec<TypeParam>, SyntaxError> {
+        self.expect("[")?;
+        let mut params = Vec::new();
+        let mut pending = Vec::new();
+
+        loop {
+            pending.push(self.expect_ident()?);
+            if self.eat(",") {
+                // `K, V any` shares one constraint between several names
+                continue;
+            }
+
+            let constraint = self.source_until(&[",", "]"])?;
+            params.extend(pending.drain(..).map(|name| TypeParam {
+                name,
+                constraint: constraint.clone(),
+            }));
+
+            if self.eat("]") {
+                break;
+            }
+            self.expect(",")?;
+            if self.eat("]") {
+                break;
+            }
+        }
+
+        Ok(params)
+    }
+
+    fn parse_type(&mut self) -> Result<TypeExpr, SyntaxError> {
+        let Some(token) = self.peek() else {
+            return Err(self.error("expected type"));
+        };
+
+        match token.text.as_str() {
+            "*" => {
+                self.index += 1;
+                Ok(TypeExpr::Pointer(Box::new(self.parse_type()?)))
+            }
+            "[" => {
+                self.index += 1;
+                if self.eat("]") {
+                    return Ok(TypeExpr::Slice(Box::new(self.parse_type()?)));
+                }
+                let len = self.source_until(&["]"])?;
+                self.expect("]")?;
+                Ok(TypeExpr::Array {
+                    len,
+                    elem: Box::new(self.parse_type()?),
+                })
+            }
+            "map" => {
+                self.index += 1;
+                self.expect("[")?;
+                let key = self.parse_type()?;
+                self.expect("]")?;
+                let value = self.parse_type()?;
+                Ok(TypeExpr::Map {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                })
+            }
+            "chan" => {
+                self.index += 1;
+                self.eat("<-");
+                Ok(TypeExpr::Chan(Box::new(self.parse_type()?)))
+            }
+            "<-" => {
+                self.index += 1;
+                self.expect("chan")?;
+                Ok(TypeExpr::Chan(Box::new(self.parse_type()?)))
+            }
+            "func" => {
+                self.index += 1;
+                self.skip_balanced("(", ")")?;
+                // Results are either a parenthesised list or a single type
+                if self.is("(") {
+                    self.skip_balanced("(", ")")?;
+                } else if self.starts_type() {
+                    self.parse_type()?;
+                }
+                Ok(TypeExpr::Func)
+            }
+            "struct" => {
+                self.index += 1;
+                Ok(TypeExpr::Struct(self.parse_struct_fields()?))
+            }
+            "interface" => {
+                self.index += 1;
+                self.skip_balanced("{", "}")?;
+                Ok(TypeExpr::Interface)
+            }
+            "(" => {
+                self.index += 1;
+                let ty = self.parse_type()?;
+                self.expect(")")?;
+                Ok(ty)
+            }
+            _ if token.kind == TokenKind::Ident => self.parse_type_name(),
+            _ => Err(self.error("expected type")),
+        }
+    }
+
+    fn parse_type_name(&mut self) -> Result<TypeExpr, SyntaxError> {
+        let mut package = None;
+        let mut name = self.expect_ident()?;
+        if self.eat(".") {
+            package = Some(name);
+            name = self.expect_ident()?;
+        }
+
+        let mut type_args = Vec::new();
+        if self.eat("[") {
+            loop {
+                type_args.push(self.parse_type()?);
+                if self.eat("]") {
+                    break;
+                }
+                self.expect(",")?;
+                if self.eat("]") {
+                    break;
+                }
+            }
+        }
+
+        Ok(TypeExpr::Named {
+            package,
+            name,
+            type_args,
+        })
+    }
+
+    fn parse_struct_fields(&mut self) -> Result<Vec<Field>, SyntaxError> {
+        self.expect("{")?;
+        let mut fields = Vec::new();
+
+        loop {
+            self.skip_semicolons();
+            if self.eat("}") {
+                break;
+            }
+            fields.push(self.parse_field()?);
+            if !self.is("}") {
+                self.expect_semicolon()?;
+            }
+        }
+
+        Ok(fields)
+    }
+
+    fn parse_field(&mut self) -> Result<Field, SyntaxError> {
+        let pos = self.current_pos();
+        let doc = self.doc_for(pos.line);
+
+        let names = if self.is_embedded_field() {
+            Vec::new()
+        } else {
+            let mut names = vec![self.expect_ident()?];
+            while self.eat(",") {
+                names.push(self.expect_ident()?);
+            }
+            names
+        };
+        let ty = self.parse_type()?;
+
+        let tag = match self.peek() {
+            Some(token) if matches!(token.kind, TokenKind::String | TokenKind::RawString) => {
+                let tag = unquote(&token.text);
+                self.index += 1;
+                Some(tag)
+            }
+            _ => None,
+        };
+        let comment = self.trailing_for(self.previous_line());
+
+        Ok(Field {
+            names,
+            ty,
+            tag,
+            doc,
+            comment,
+            pos,
+        })
+    }
+
+    // Embedded fields are a (possibly qualified, pointer or generic) type name alone
+    fn is_embedded_field(&self) -> bool {
+        if self.is("*") {
+            return true;
+        }
+        if !self.peek().is_some_and(|t| t.kind == TokenKind::Ident) {
+            return false;
+        }
+
+        match self.peek_at(1) {
+            None => true,
+            Some(next) if next.text == "." => true,
+            Some(next) if next.text == "[" => {
+                // `Base[T]` is embedded, `Items [4]int` is a named array field
+                let mut depth = 0;
+                for (offset, token) in self.tokens[self.index + 1..].iter().enumerate() {
+                    match token.text.as_str() {
+                        "[" => depth += 1,
+                        "]" => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return self
+                                    .peek_at(offset + 2)
+                                    .is_none_or(Self::ends_field);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                false
+            }
+            Some(next) => Self::ends_field(next),
+        }
+    }
+
+    fn ends_field(token: &Token) -> bool {
+        matches!(
+            token.kind,
+            TokenKind::Semicolon | TokenKind::String | TokenKind::RawString
+        ) || token.text == "}"
+    }
+
+    fn parse_const_decl(&mut self, consts: &mut Vec<ConstDecl>) -> Result<(), SyntaxError> {
+        let decl_doc = self.doc_for(self.current_pos().line);
+        self.expect("const")?;
+        let mut decl = ConstDecl::default();
+
+        if self.eat("(") {
+            loop {
+                self.skip_semicolons();
+                if self.eat(")") {
+                    break;
+                }
+                let doc = self.doc_for(self.current_pos().line);
+                decl.specs.push(self.parse_const_spec(doc)?);
+            }
+        } else {
+            decl.specs.push(self.parse_const_spec(decl_doc)?);
+        }
+
+        consts.push(decl);
+        Ok(())
+    }
+
+    fn parse_const_spec(&mut self, doc: Option<CommentGroup>) -> Result<ConstSpec, SyntaxError> {
+        let pos = self.current_pos();
+        let mut names = vec![self.expect_ident()?];
+        while self.eat(",") {
+            names.push(self.expect_ident()?);
+        }
+
+        let ty = if self.is("=") || self.at_spec_end() {
+            None
+        } else {
+            Some(self.parse_type()?)
+        };
+
+        let mut values = Vec::new();
+        if self.eat("=") {
+            loop {
+                values.push(self.source_until(&[",", ")"])?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+        let comment = self.trailing_for(self.previous_line());
+
+        Ok(ConstSpec {
+            names,
+            ty,
+            values,
+            doc,
+            comment,
+            pos,
+        })
+    }
+
+    fn parse_func_decl(&mut self) -> Result<FuncDecl, SyntaxError> {
+        let pos = self.current_pos();
+        let doc = self.doc_for(pos.line);
+        self.expect("func")?;
+
+        let receiver = if self.eat("(") {
+            // Skip the receiver name, if any
+            if self.peek().is_some_and(|t| t.kind == TokenKind::Ident)
+                && self
+                    .peek_at(1)
+                    .is_some_and(|t| !matches!(t.text.as_str(), ")" | "." | "["))
+            {
+                self.index += 1;
+            }
+            let receiver = self.parse_type()?;
+            self.expect(")")?;
+            Some(receiver)
+        } else {
+            None
+        };
+        let name = self.expect_ident()?;
+
+        // Type parameters, signature and body
+        self.skip_declaration();
+
+        Ok(FuncDecl {
+            name,
+            receiver,
+            doc,
+            pos,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.index + offset)
+    }
+
+    fn peek_text(&self) -> &str {
+        self.peek().map_or("", |t| t.text.as_str())
+    }
+
+    fn at_end(&self) -> bool {
+        self.index >= self.tokens.len()
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.peek()
+            .is_some_and(|t| t.text == text && matches!(t.kind, TokenKind::Operator | TokenKind::Ident))
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let matched = self.is(text);
+        if matched {
+            self.index += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), SyntaxError> {
+        if self.eat(text) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", text)))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, SyntaxError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Ident => {
+                let name = token.text.clone();
+                self.index += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    fn expect_semicolon(&mut self) -> Result<(), SyntaxError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Semicolon => {
+                self.index += 1;
+                Ok(())
+            }
+            _ => Err(self.error("expected end of line")),
+        }
+    }
+
+    fn skip_semicolons(&mut self) {
+        while self.peek().is_some_and(|t| t.kind == TokenKind::Semicolon) {
+            self.index += 1;
+        }
+    }
+
+    fn at_spec_end(&self) -> bool {
+        self.peek()
+            .is_none_or(|t| t.kind == TokenKind::Semicolon || t.text == ")")
+    }
+
+    fn starts_type(&self) -> bool {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Ident => true,
+            Some(token) => matches!(token.text.as_str(), "*" | "[" | "(" | "<-"),
+            None => false,
+        }
+    }
+
+    // Skip tokens up to and including the semicolon that ends the current declaration
+    fn skip_declaration(&mut self) {
+        let start = self.index;
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek() {
+            match token.text.as_str() {
+                "(" | "[" | "{" if token.kind == TokenKind::Operator => depth += 1,
+                ")" | "]" | "}" if token.kind == TokenKind::Operator => {
+                    depth = depth.saturating_sub(1)
+                }
+                _ if token.kind == TokenKind::Semicolon && depth == 0 && self.index > start => {
+                    self.index += 1;
+                    return;
+                }
+                _ => {}
+            }
+            self.index += 1;
+        }
+    }
+
+    // Skip a bracketed group starting at the current token
+    fn skip_balanced(&mut self, open: &str, close: &str) -> Result<(), SyntaxError> {
+        self.expect(open)?;
+        let mut depth = 1;
+        while let Some(token) = self.peek() {
+            if token.kind == TokenKind::Operator {
+                if token.text == open {
+                    depth += 1;
+                } else if token.text == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.index += 1;
+                        return Ok(());
+                    }
+                }
+            }
+            self.index += 1;
+        }
+        Err(self.error(&format!("expected `{}`", close)))
+    }
+
+    // Source text of the tokens before the first of `stops` (or a semicolon) outside brackets
+    fn source_until(&mut self, stops: &[&str]) -> Result<String, SyntaxError> {
+        let start = self.index;
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek() {
+            let is_operator = token.kind == TokenKind::Operator;
+            if depth == 0
+                && (token.kind == TokenKind::Semicolon
+                    || (is_operator && stops.contains(&token.text.as_str())))
+            {
+                break;
+            }
+            match token.text.as_str() {
+                "(" | "[" | "{" if is_operator => depth += 1,
+                ")" | "]" | "}" if is_operator => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.index += 1;
+        }
+
+        if self.index == start {
+            return Err(self.error("expected expression"));
+        }
+        let first = &self.tokens[start];
+        let last = &self.tokens[self.index - 1];
+        Ok(self.source[first.offset..last.offset + last.text.len()].to_string())
+    }
+
+    fn current_pos(&self) -> Position {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map_or_else(Position::default, |t| t.pos)
+    }
+
+    fn previous_line(&self) -> usize {
+        self.index
+            .checked_sub(1)
+            .and_then(|index| self.tokens.get(index))
+            .map_or(0, |token| token.pos.line + token.text.matches('\n').count())
+    }
+
+    fn error(&self, message: &str) -> SyntaxError {
+        let found = self.peek().map_or_else(
+            || "end of file".to_string(),
+            |t| match t.kind {
+                TokenKind::Semicolon if t.text == "\n" => "newline".to_string(),
+                _ => format!("`{}`", t.text),
+            },
+        );
+        SyntaxError {
+            message: format!("{}, found {}", message, found),
+            pos: self.current_pos(),
+        }
+    }
+
+    // The comment group ending on the line just above `line`
+    fn doc_for(&self, line: usize) -> Option<CommentGroup> {
+        self.doc_groups
+            .iter()
+            .find(|group| group.end_line() + 1 == line && group.start_line() < line)
+            .cloned()
+    }
+
+    // A comment following code on `line`
+    fn trailing_for(&self, line: usize) -> Option<CommentGroup> {
+        self.trailing
+            .iter()
+            .find(|comment| comment.pos.line == line)
+            .map(|comment| CommentGroup {
+                comments: vec![comment.clone()],
+            })
+    }
+}
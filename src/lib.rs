@@ -1,4 +1,7 @@
+pub mod formatter;
 pub mod generator;
+pub mod linter;
+pub mod go_ast;
 pub mod models;
 pub mod parser;
 
@@ -8,7 +11,19 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    use crate::models::{ParsedOperation, Schema};
     use crate::parser::GoParser;
+    use std::collections::HashMap;
+
+    /// Write `source` to a temporary main.go and parse its operations and schemas
+    fn parse_source(source: &str) -> (Vec<ParsedOperation>, HashMap<String, Schema>) {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("main.go"), source).unwrap();
+
+        GoParser::new()
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap()
+    }
 
     #[test]
     fn test_parse_general_api_info() {
@@ -41,9 +56,9 @@ package main
 // @produce json
 // @schemes http https
 
-func main() {
+func main() {{
     // ...
-}
+}}
         "#
         )
         .unwrap();
@@ -60,7 +75,7 @@ func main() {
             Some("This is a sample server.".to_string())
         );
         assert_eq!(
-            api_info.info.terms_of_service,
+            api_info.info.termsOfService,
             Some("http://swagger.io/terms/".to_string())
         );
 
@@ -104,25 +119,1610 @@ func main() {
         writeln!(file, "// @tags test").unwrap();
         writeln!(file, "// @produce application/json").unwrap();
         writeln!(file, "// @router /test [get]").unwrap();
-        writeln!(file, "func test() {}").unwrap();
+        writeln!(file, "func test() {{}}").unwrap();
 
         // Parse operations
         let parser = crate::parser::GoParser::new();
-        let operations = parser.parse_operations(&["."], dir.path()).unwrap();
+        let (operations, _schemas) = parser
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
 
         // Verify the parsed operations
         assert_eq!(operations.len(), 1);
 
-        let op = operations.iter().next().unwrap();
-        assert_eq!(op.path, "test");
+        let op = operations.first().unwrap();
+        assert_eq!(op.path, "/test");
         assert_eq!(op.operation.summary, Some("Test endpoint".to_string()));
         assert_eq!(
             op.operation.description,
             Some("Test description".to_string())
         );
-        assert_eq!(op.operation.consumes, vec!["application/json"]);
+        assert!(op.operation.consumes.is_empty());
         assert_eq!(op.operation.produces, vec!["application/json"]);
         assert_eq!(op.operation.parameters.len(), 0);
-        assert!(op.operation.responses.contains_key("200"));
+        assert!(op.operation.responses.is_empty());
+    }
+
+    #[test]
+    fn test_json_struct_tags() {
+        let (_operations, schemas) = parse_source(
+            r#"
+package main
+
+type User struct {
+    UserID   string `json:"user_id" example:"u-1" binding:"required"`
+    Nickname string `json:"nickname,omitempty"`
+    Email    string `json:",omitempty"`
+    Password string `json:"-"`
+    Age      *int
+}
+
+// @Success 200 {object} User
+// @Router /users [get]
+func getUser() {}
+"#,
+        );
+
+        let user = &schemas["User"];
+        let mut properties: Vec<&str> = user.properties.keys().map(|k| k.as_str()).collect();
+        properties.sort();
+        assert_eq!(properties, vec!["Age", "Email", "nickname", "user_id"]);
+        assert_eq!(user.required, Some(vec!["user_id".to_string()]));
+    }
+
+    #[test]
+    fn test_validation_struct_tags() {
+        let (_operations, schemas) = parse_source(
+            r#"
+package main
+
+type SignupRequest struct {
+    Username string   `json:"username" binding:"required,min=3,max=64"`
+    Email    *string  `json:"email" validate:"required,email"`
+    Age      int      `json:"age" validate:"omitempty,gte=18,lte=130"`
+    Role     string   `json:"role" binding:"oneof=admin member guest"`
+    Level    int      `json:"level" binding:"oneof=1 2 3"`
+    Tags     []string `json:"tags" validate:"max=5,dive,len=4"`
+    Labels   map[string]string `json:"labels" validate:"dive,keys,min=2,endkeys,max=10"`
+    Team     Team     `json:"team" binding:"min=1"`
+}
+
+type Team struct {
+    Name string `json:"name"`
+}
+
+// @Success 200 {object} SignupRequest
+// @Router /signup [post]
+func signup() {}
+"#,
+        );
+
+        let schema = &schemas["SignupRequest"];
+        let username = &schema.properties["username"];
+        assert_eq!(username.minLength, Some(3));
+        assert_eq!(username.maxLength, Some(64));
+        assert_eq!(
+            schema.properties["email"].format,
+            Some("email".to_string())
+        );
+        assert_eq!(schema.properties["age"].minimum, Some(18.0));
+        assert_eq!(schema.properties["age"].maximum, Some(130.0));
+        assert_eq!(
+            schema.properties["role"].enum_values,
+            Some(vec!["admin".into(), "member".into(), "guest".into()])
+        );
+        assert_eq!(
+            schema.properties["level"].enum_values,
+            Some(vec![1.into(), 2.into(), 3.into()])
+        );
+        let tags = &schema.properties["tags"];
+        assert_eq!(tags.maxItems, Some(5));
+        assert_eq!(tags.items.as_ref().unwrap().minLength, Some(4));
+        let labels = &schema.properties["labels"];
+        assert_eq!(labels.propertyNames.as_ref().unwrap().minLength, Some(2));
+        assert_eq!(labels.additionalProperties.as_ref().unwrap()["maxLength"], 10);
+        let team = &schema.properties["team"];
+        assert!(team.ref_.is_some());
+        assert!(team.minimum.is_none() && team.minProperties.is_none());
+
+        let mut required = schema.required.clone().unwrap();
+        required.sort();
+        // Only explicit `required` rules make properties required
+        assert_eq!(required, vec!["email", "username"]);
+    }
+
+    #[test]
+    fn test_embedded_struct_fields() {
+        let source = r#"
+package main
+
+type BaseModel struct {
+    ID        string `json:"id"`
+    CreatedAt string `json:"created_at" binding:"required"`
+}
+
+type Audit struct {
+    UpdatedBy string `json:"updated_by" binding:"required"`
+}
+
+type User struct {
+    BaseModel
+    *Audit
+    Name string `json:"name" binding:"required"`
+    ID   int    `json:"id" binding:"required"`
+}
+
+// @Success 200 {object} User
+// @Router /users [get]
+func getUser() {}
+"#;
+        let (_operations, schemas) = parse_source(source);
+        let user = &schemas["User"];
+        let mut properties: Vec<&str> = user.properties.keys().map(|k| k.as_str()).collect();
+        properties.sort();
+        assert_eq!(properties, vec!["created_at", "id", "name", "updated_by"]);
+        // The outer ID shadows the embedded one
+        assert_eq!(
+            user.properties["id"].type_,
+            Some(serde_json::Value::String("integer".to_string()))
+        );
+        let mut required = user.required.clone().unwrap();
+        required.sort();
+        assert_eq!(required, vec!["created_at", "id", "name"]);
+
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("main.go"), source).unwrap();
+        let (_operations, schemas) = GoParser::new()
+            .with_embedded_all_of(true)
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+        let all_of = schemas["User"].allOf.as_ref().unwrap();
+        assert_eq!(all_of.len(), 3);
+        assert_eq!(
+            all_of[0].ref_,
+            Some("#/components/schemas/BaseModel".to_string())
+        );
+        assert_eq!(all_of[1].ref_, Some("#/components/schemas/Audit".to_string()));
+        assert!(all_of[2].properties.contains_key("name"));
+        assert!(schemas.contains_key("BaseModel"));
+    }
+
+    #[test]
+    fn test_response_composition() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+type Response struct {
+    Code int         `json:"code"`
+    Data interface{} `json:"data"`
+}
+
+type User struct {
+    Name string `json:"name"`
+}
+
+type Meta struct {
+    Total int `json:"total"`
+}
+
+// @Success 200 {object} Response{data=[]User, meta=Meta{total=string}} "users"
+// @Router /users [get]
+func listUsers() {}
+"#,
+        );
+
+        let response = &operations[0].operation.responses["200"];
+        assert_eq!(response.description, "users");
+        let schema = response.content["application/json"].schema.as_ref().unwrap();
+        let all_of = schema.allOf.as_ref().unwrap();
+        assert_eq!(
+            all_of[0].ref_,
+            Some("#/components/schemas/Response".to_string())
+        );
+
+        let data = &all_of[1].properties["data"];
+        assert_eq!(
+            data.items.as_ref().unwrap().ref_,
+            Some("#/components/schemas/User".to_string())
+        );
+        let meta = all_of[1].properties["meta"].allOf.as_ref().unwrap();
+        assert_eq!(meta[0].ref_, Some("#/components/schemas/Meta".to_string()));
+        assert_eq!(
+            meta[1].properties["total"].type_,
+            Some(serde_json::Value::String("string".to_string()))
+        );
+
+        assert!(schemas.contains_key("User"));
+        assert!(schemas.contains_key("Meta"));
+    }
+
+    #[test]
+    fn test_generic_struct_instantiation() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+type Page[T any] struct {
+    Items []T `json:"items"`
+    Total int `json:"total"`
+}
+
+type Pair[K comparable, V any] struct {
+    Key   K `json:"key"`
+    Value V `json:"value"`
+}
+
+type User struct {
+    Name string `json:"name"`
+}
+
+// @Success 200 {object} Page[User]
+// @Router /users [get]
+func listUsers() {}
+
+// @Success 200 {object} Pair[string, Page[User]]
+// @Router /pairs [get]
+func getPair() {}
+"#,
+        );
+
+        let response = &operations[0].operation.responses["200"];
+        assert_eq!(
+            response.content["application/json"].schema.as_ref().unwrap().ref_,
+            Some("#/components/schemas/Page-User".to_string())
+        );
+
+        let page = &schemas["Page-User"];
+        assert_eq!(
+            page.properties["items"].items.as_ref().unwrap().ref_,
+            Some("#/components/schemas/User".to_string())
+        );
+        assert!(schemas.contains_key("User"));
+
+        let pair = &schemas["Pair-string-Page-User"];
+        assert_eq!(
+            pair.properties["key"].type_,
+            Some(serde_json::Value::String("string".to_string()))
+        );
+        assert_eq!(
+            pair.properties["value"].ref_,
+            Some("#/components/schemas/Page-User".to_string())
+        );
+        assert!(!schemas.keys().any(|name| name.contains('[')));
+    }
+
+    #[test]
+    fn test_map_fields() {
+        let (_operations, schemas) = parse_source(
+            r#"
+package main
+
+type Item struct {
+    SKU string `json:"sku"`
+}
+
+type Catalog struct {
+    Labels map[string]string        `json:"labels"`
+    Index  map[string][]*Item       `json:"index"`
+    Counts map[string]map[string]int `json:"counts"`
+    Meta   map[string]interface{}   `json:"meta"`
+}
+
+// @Success 200 {object} Catalog
+// @Router /catalog [get]
+func getCatalog() {}
+"#,
+        );
+
+        let catalog = &schemas["Catalog"];
+        let object = Some(serde_json::Value::String("object".to_string()));
+        assert_eq!(catalog.properties["labels"].type_, object);
+        assert_eq!(
+            catalog.properties["labels"].additionalProperties,
+            Some(serde_json::json!({"type": "string"}))
+        );
+        assert_eq!(
+            catalog.properties["index"].additionalProperties,
+            Some(serde_json::json!({
+                "type": "array",
+                "items": {"$ref": "#/components/schemas/Item"}
+            }))
+        );
+        assert_eq!(
+            catalog.properties["counts"].additionalProperties,
+            Some(serde_json::json!({
+                "type": "object",
+                "additionalProperties": {"type": "integer"}
+            }))
+        );
+        assert_eq!(
+            catalog.properties["meta"].additionalProperties,
+            Some(serde_json::json!({}))
+        );
+        assert!(schemas.contains_key("Item"));
+    }
+
+    #[test]
+    fn test_named_type_enums() {
+        let (_operations, schemas) = parse_source(
+            r#"
+package main
+
+type OrderStatus string
+
+const (
+    StatusPending  OrderStatus = "pending" // Waiting for payment
+    StatusShipped  OrderStatus = "shipped"
+    StatusArchived OrderStatus = "archived" // Kept for auditing
+)
+
+type Priority int
+
+const (
+    PriorityLow Priority = iota + 1
+    PriorityMedium
+    _
+    PriorityHigh
+)
+
+type Order struct {
+    Status   OrderStatus `json:"status"`
+    Priority Priority    `json:"priority"`
+}
+
+// @Success 200 {object} Order
+// @Router /orders [get]
+func getOrder() {}
+"#,
+        );
+
+        let status = &schemas["OrderStatus"];
+        assert_eq!(
+            status.type_,
+            Some(serde_json::Value::String("string".to_string()))
+        );
+        assert_eq!(
+            status.enum_values,
+            Some(vec!["pending".into(), "shipped".into(), "archived".into()])
+        );
+        assert_eq!(
+            status.extensions["x-enum-varnames"],
+            serde_json::json!(["StatusPending", "StatusShipped", "StatusArchived"])
+        );
+        assert_eq!(
+            status.extensions["x-enum-descriptions"],
+            serde_json::json!(["Waiting for payment", "", "Kept for auditing"])
+        );
+
+        let priority = &schemas["Priority"];
+        assert_eq!(
+            priority.type_,
+            Some(serde_json::Value::String("integer".to_string()))
+        );
+        assert_eq!(
+            priority.enum_values,
+            Some(vec![1.into(), 2.into(), 4.into()])
+        );
+        assert_eq!(
+            schemas["Order"].properties["status"].ref_,
+            Some("#/components/schemas/OrderStatus".to_string())
+        );
+    }
+
+    #[test]
+    fn test_well_known_types_and_overrides() {
+        let source = r#"
+package main
+
+type Event struct {
+    ID        uuid.UUID       `json:"id"`
+    At        time.Time       `json:"at"`
+    EndsAt    *time.Time      `json:"ends_at"`
+    Payload   []byte          `json:"payload"`
+    Raw       json.RawMessage `json:"raw"`
+    Note      sql.NullString  `json:"note"`
+    Amount    decimal.Decimal `json:"amount"`
+}
+
+// @Param since query time.Time false "lower bound"
+// @Success 200 {object} Event
+// @Router /events [get]
+func listEvents() {}
+"#;
+        let (operations, schemas) = parse_source(source);
+        let event = &schemas["Event"];
+        assert_eq!(event.properties["id"].format, Some("uuid".to_string()));
+        assert_eq!(event.properties["at"].format, Some("date-time".to_string()));
+        assert_eq!(event.properties["ends_at"].format, Some("date-time".to_string()));
+        assert_eq!(event.properties["payload"].format, Some("byte".to_string()));
+        assert!(event.properties["raw"].type_.is_none());
+        assert_eq!(
+            event.properties["note"].type_,
+            Some(serde_json::json!(["string", "null"]))
+        );
+        assert!(!schemas.contains_key("time.Time"));
+        assert_eq!(
+            operations[0].operation.parameters[0]
+                .schema
+                .as_ref()
+                .unwrap()
+                .format,
+            Some("date-time".to_string())
+        );
+
+        let overrides = crate::parser::TypeOverrides::parse(
+            "// project overrides\n\
+             replace github.com/shopspring/decimal.Decimal number double\n\
+             skip database/sql.NullString\n",
+        )
+        .unwrap();
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("main.go"), source).unwrap();
+        let (_operations, schemas) = GoParser::new()
+            .with_type_overrides(overrides)
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+        let event = &schemas["Event"];
+        assert_eq!(
+            event.properties["amount"].type_,
+            Some(serde_json::Value::String("number".to_string()))
+        );
+        assert_eq!(event.properties["amount"].format, Some("double".to_string()));
+        assert!(!event.properties.contains_key("note"));
+
+        assert!(crate::parser::TypeOverrides::parse("rename a b").is_err());
+    }
+
+    #[test]
+    fn test_go_declaration_parsing() {
+        let source = r#"
+package main
+
+import (
+    "time"
+    api "example.com/app/api" // aliased
+)
+
+type (
+    // Tag is a label { not a struct body }
+    Tag string
+
+    Address struct {
+        Street, City string
+        /* closing brace } inside a comment */
+        Zip string `json:"zip"
+            validate:"len=5"`
+    }
+)
+
+type Profile struct {
+    Address Address `json:"address"`
+    Links []struct {
+        Rel  string `json:"rel" binding:"required"`
+        Href string `json:"href" example:"https://example.com/{id}" binding:"required"`
+    } `json:"links"`
+    Settings struct {
+        Theme string `json:"theme,omitempty"`
+    } `json:"settings"`
+    Callback func(
+        at time.Time,
+    ) error `json:"-"`
+}
+
+const banner = `
+}  not the end of anything {
+`
+
+// @Success 200 {object} Profile
+// @Router /profile [get]
+func (h *handler) getProfile(c *api.Context) {
+    if true { _ = "}" }
+}
+"#;
+        let file = crate::go_ast::parse_file(source).unwrap();
+        assert!(file.errors.is_empty(), "{:?}", file.errors);
+        assert_eq!(file.imports[1].package_name(), "api");
+        let type_names: Vec<&str> = file.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(type_names, vec!["Tag", "Address", "Profile"]);
+        assert_eq!(
+            file.types[0].doc.as_ref().unwrap().text(),
+            "Tag is a label { not a struct body }"
+        );
+        assert_eq!(file.funcs[0].name, "getProfile");
+        assert_eq!(file.funcs[0].receiver.as_ref().unwrap().to_string(), "*handler");
+
+        let (operations, schemas) = parse_source(source);
+        assert_eq!(operations.len(), 1);
+
+        let address = &schemas["Address"];
+        let mut address_fields: Vec<&String> = address.properties.keys().collect();
+        address_fields.sort();
+        assert_eq!(address_fields, vec!["City", "Street", "zip"]);
+        assert_eq!(address.properties["zip"].minLength, Some(5));
+
+        let profile = &schemas["Profile"];
+        let links = profile.properties["links"].items.as_ref().unwrap();
+        assert_eq!(links.properties["href"].type_, Some(serde_json::json!("string")));
+        assert_eq!(links.required, Some(vec!["rel".to_string(), "href".to_string()]));
+        assert!(profile.properties["settings"].properties.contains_key("theme"));
+        assert!(!profile.properties.contains_key("Callback"));
+        assert!(!schemas.contains_key("struct{}"));
+    }
+
+    #[test]
+    fn test_doc_comment_descriptions() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+// User is a registered account.
+//
+// Deprecated: use Account instead.
+type User struct {
+    // Unique identifier
+    ID   string `json:"id"`
+    Name string `json:"name"` // Display name
+}
+
+// GetUser returns a single user. It fails when the user is unknown.
+//
+// The lookup is case-insensitive.
+// @Success 200 {object} User
+// @Router /users/{id} [get]
+func GetUser() {}
+
+// ListUsers godoc
+// @Summary List users
+// @Description Paginated list
+// @Success 200 {array} User
+// @Router /users [get]
+func ListUsers() {}
+"#,
+        );
+
+        let user = &schemas["User"];
+        assert_eq!(
+            user.description.as_deref(),
+            Some("User is a registered account.\n\nDeprecated: use Account instead.")
+        );
+        assert_eq!(user.deprecated, Some(true));
+        assert_eq!(
+            user.properties["id"].description.as_deref(),
+            Some("Unique identifier")
+        );
+        assert_eq!(
+            user.properties["name"].description.as_deref(),
+            Some("Display name")
+        );
+
+        let get_user = operations.iter().find(|op| op.path == "/users/{id}").unwrap();
+        assert_eq!(
+            get_user.operation.summary.as_deref(),
+            Some("GetUser returns a single user.")
+        );
+        assert_eq!(
+            get_user.operation.description.as_deref(),
+            Some("GetUser returns a single user. It fails when the user is unknown.\n\nThe lookup is case-insensitive.")
+        );
+
+        let list_users = operations.iter().find(|op| op.path == "/users").unwrap();
+        assert_eq!(list_users.operation.summary.as_deref(), Some("List users"));
+        assert_eq!(
+            list_users.operation.description.as_deref(),
+            Some("Paginated list")
+        );
+    }
+
+    #[test]
+    fn test_response_headers() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Success 200 {object} string
+// @Failure 429 {object} string
+// @Failure 500 {object} string
+// @Header 200 {string} X-Request-ID "trace id"
+// @Header 200,429 {integer} X-RateLimit-Remaining "requests left"
+// @Header all {string} X-Server
+// @Header 404 {string} X-Ignored "no such response"
+// @Router /users [get]
+func listUsers() {}
+"#,
+        );
+
+        let responses = &operations[0].operation.responses;
+        let ok = &responses["200"].headers;
+        assert_eq!(ok["X-Request-ID"].description.as_deref(), Some("trace id"));
+        assert_eq!(
+            ok["X-RateLimit-Remaining"].schema.as_ref().unwrap().type_,
+            Some(serde_json::Value::String("integer".to_string()))
+        );
+        assert!(responses["429"].headers.contains_key("X-RateLimit-Remaining"));
+        assert!(!responses["500"].headers.contains_key("X-RateLimit-Remaining"));
+        for response in responses.values() {
+            assert!(response.headers["X-Server"].description.is_none());
+        }
+        assert!(!responses.contains_key("404"));
+    }
+    #[test]
+    fn test_deprecated_operations() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Summary List users
+// @Deprecated
+// @Deprecated.sunset 2027-01-01
+// @Deprecated.replacement /v2/users
+// @Router /users [get]
+func listUsers() {}
+
+// GetUser returns a user.
+//
+// Deprecated: use GetUserV2 instead.
+// @Router /users/{id} [get]
+func GetUser() {}
+
+// @Router /v2/users [get]
+func listUsersV2() {}
+"#,
+        );
+
+        let by_path = |path: &str| operations.iter().find(|op| op.path == path).unwrap();
+        let list = &by_path("/users").operation;
+        assert_eq!(list.deprecated, Some(true));
+        assert_eq!(list.extensions["x-sunset"], serde_json::json!("2027-01-01"));
+        assert_eq!(list.extensions["x-replaced-by"], serde_json::json!("/v2/users"));
+
+        let json = serde_json::to_value(list).unwrap();
+        assert_eq!(json["x-sunset"], serde_json::json!("2027-01-01"));
+
+        assert_eq!(by_path("/users/{id}").operation.deprecated, Some(true));
+        let current = &by_path("/v2/users").operation;
+        assert_eq!(current.deprecated, None);
+        assert!(current.extensions.is_empty());
+    }
+    #[test]
+    fn test_vendor_extensions() {
+        let source = r#"
+package main
+
+// @title Extensions API
+// @version 1.0
+// @x-logo {"url": "https://example.com/logo.png"}
+// @x-internal true
+// @tag.name users
+// @tag.x-displayName Users
+// @securityDefinitions.apikey ApiKeyAuth
+// @x-gateway-key header
+func main() {}
+
+type User struct {
+	Nickname string `json:"nickname" extensions:"x-nullable,x-order=1,!x-omitempty"`
+}
+
+// @x-codegen-request-body-name body
+// @x-rate-limit {"rps": 10}
+// @Success 200 {object} User
+// @Router /users [get]
+func listUsers() {}
+"#;
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("main.go");
+        std::fs::write(&file_path, source).unwrap();
+
+        let parser = GoParser::new();
+        let api_info = parser.parse_general_api_info(&file_path).unwrap();
+        assert_eq!(
+            api_info.info.extensions["x-logo"],
+            serde_json::json!({"url": "https://example.com/logo.png"})
+        );
+        assert_eq!(api_info.extensions["x-internal"], serde_json::json!(true));
+        assert_eq!(api_info.tags[0].extensions["x-displayName"], serde_json::json!("Users"));
+        assert_eq!(
+            api_info.security_definitions["ApiKeyAuth"].extensions["x-gateway-key"],
+            serde_json::json!("header")
+        );
+
+        let (operations, schemas) = parser
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+        let operation = serde_json::to_value(&operations[0].operation).unwrap();
+        assert_eq!(operation["x-codegen-request-body-name"], serde_json::json!("body"));
+        assert_eq!(operation["x-rate-limit"], serde_json::json!({"rps": 10}));
+
+        let nickname = serde_json::to_value(&schemas["User"].properties["nickname"]).unwrap();
+        assert_eq!(nickname["x-nullable"], serde_json::json!(true));
+        assert_eq!(nickname["x-order"], serde_json::json!(1));
+        assert_eq!(nickname["x-omitempty"], serde_json::json!(false));
+    }
+    #[test]
+    fn test_callbacks() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+type Subscription struct {
+	CallbackURL string `json:"callbackUrl"`
+}
+
+type PaymentEvent struct {
+	ID string `json:"id"`
+}
+
+type Ack struct {
+	Received bool `json:"received"`
+}
+
+// @Param subscription body Subscription true "subscription"
+// @Success 201 {object} Subscription
+// @Callback onPaymentEvent {$request.body#/callbackUrl} [post] PaymentEvent "payment event"
+// @Callback.param onPaymentEvent X-Signature header string true "HMAC signature"
+// @Callback.success onPaymentEvent 200 {object} Ack "acknowledged"
+// @Callback.failure onPaymentEvent 410 {string} string "unsubscribe"
+// @Router /subscriptions [post]
+func subscribe() {}
+"#,
+        );
+
+        let operation = &operations[0].operation;
+        let path_item = &operation.callbacks["onPaymentEvent"]["{$request.body#/callbackUrl}"];
+        let callback = path_item.post.as_ref().unwrap();
+        assert!(callback.operationId.is_none());
+
+        let body = callback.requestBody.as_ref().unwrap();
+        assert_eq!(body.description.as_deref(), Some("payment event"));
+        assert_eq!(
+            body.content["application/json"].schema.as_ref().unwrap().ref_.as_deref(),
+            Some("#/components/schemas/PaymentEvent")
+        );
+        assert_eq!(callback.parameters[0].name, "X-Signature");
+        assert_eq!(callback.parameters[0].in_type, "header");
+        assert_eq!(callback.responses["200"].description, "acknowledged");
+        assert!(callback.responses.contains_key("410"));
+
+        // Models used only by the callback still end up in the components
+        assert!(schemas.contains_key("PaymentEvent"));
+        assert!(schemas.contains_key("Ack"));
+    }
+    #[test]
+    fn test_webhooks() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+type Pet struct {
+	Name string `json:"name"`
+}
+
+// @Summary A pet was added
+// @Param pet body Pet true "the new pet"
+// @Success 200 {string} string "received"
+// @Security ApiKeyAuth
+// @Webhook newPet [post]
+func newPetHook() {}
+
+// @Router /pets [get]
+func listPets() {}
+"#,
+        );
+
+        let hook = operations.iter().find(|op| op.webhook).unwrap();
+        assert_eq!(hook.path, "newPet");
+        assert_eq!(hook.operation.operationId.as_deref(), Some("post_newPet"));
+
+        let dir = tempdir().unwrap();
+        let generator = crate::generator::Generator::new(
+            crate::models::ParsedApiInfo::new(),
+            operations,
+            schemas,
+            "3.1.1".to_string(),
+        );
+        generator.generate(dir.path(), &["json".to_string()]).unwrap();
+        let spec: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("openapi.json")).unwrap())
+                .unwrap();
+
+        let webhook = &spec["webhooks"]["newPet"]["post"];
+        assert_eq!(webhook["summary"], "A pet was added");
+        assert_eq!(webhook["security"][0]["ApiKeyAuth"], serde_json::json!([]));
+        assert_eq!(
+            webhook["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+        assert!(spec["paths"].get("newPet").is_none());
+        assert!(spec["paths"]["/pets"]["get"].is_object());
+        assert!(spec["components"]["schemas"]["Pet"].is_object());
+    }
+    #[test]
+    fn test_multiple_routers() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Summary Get a user
+// @Router /users/{id} [get]
+// @Router /v1/users/{id} [get]
+// @DeprecatedRouter /legacy/users/{id} [get]
+func getUser() {}
+
+// @ID listUsers
+// @Router /users [get]
+// @Router /v1/users [get]
+func listUsers() {}
+
+// @ID listUsers
+// @Router /admin/users [get]
+func listAdminUsers() {}
+"#,
+        );
+
+        assert_eq!(operations.len(), 6);
+        let by_path = |path: &str| &operations.iter().find(|op| op.path == path).unwrap().operation;
+
+        assert_eq!(by_path("/users/{id}").summary.as_deref(), Some("Get a user"));
+        assert_eq!(by_path("/v1/users/{id}").summary.as_deref(), Some("Get a user"));
+        assert_eq!(by_path("/users/{id}").deprecated, None);
+        assert_eq!(by_path("/legacy/users/{id}").deprecated, Some(true));
+        assert_ne!(
+            by_path("/users/{id}").operationId,
+            by_path("/v1/users/{id}").operationId
+        );
+
+        assert_eq!(by_path("/users").operationId.as_deref(), Some("listUsers"));
+        assert_eq!(by_path("/v1/users").operationId.as_deref(), Some("listUsers_2"));
+        // Duplicates across handlers are renamed as well
+        assert_eq!(by_path("/admin/users").operationId.as_deref(), Some("listUsers_3"));
+        assert_eq!(by_path("/users/{id}").operationId.as_deref(), Some("get_users_id"));
+    }
+    #[test]
+    fn test_path_parameter_inference() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Param id path int true "user id"
+// @Param stale path string true "not in the route"
+// @Router /users/:id/files/*filepath [get]
+func getFile() {}
+
+// @Router /articles/{category}/{id:[0-9]{1,8}} [get]
+func getArticle() {}
+"#,
+        );
+
+        let file = operations.iter().find(|op| op.path.starts_with("/users")).unwrap();
+        assert_eq!(file.path, "/users/{id}/files/{filepath}");
+        let params = &file.operation.parameters;
+        assert_eq!(params.len(), 2);
+        let id = params.iter().find(|p| p.name == "id").unwrap();
+        assert_eq!(id.description.as_deref(), Some("user id"));
+        let filepath = params.iter().find(|p| p.name == "filepath").unwrap();
+        assert_eq!(filepath.in_type, "path");
+        assert_eq!(filepath.required, Some(true));
+        assert_eq!(
+            filepath.schema.as_ref().unwrap().type_,
+            Some(serde_json::Value::String("string".to_string()))
+        );
+
+        let article = operations.iter().find(|op| op.path.starts_with("/articles")).unwrap();
+        assert_eq!(article.path, "/articles/{category}/{id}");
+        let id = article.operation.parameters.iter().find(|p| p.name == "id").unwrap();
+        assert_eq!(id.schema.as_ref().unwrap().pattern.as_deref(), Some("^[0-9]{1,8}$"));
+        assert!(article.operation.parameters.iter().any(|p| p.name == "category"));
+
+        // Both kinds of mismatch are problems for --strict
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.go"),
+            "package main\n\n// @Param stale path string true \"gone\"\n// @Router /users/{id} [get]\nfunc getUser() {}\n",
+        )
+        .unwrap();
+        let parser = GoParser::new();
+        parser
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+        let problems: Vec<String> = parser.take_problems().iter().map(|p| p.to_string()).collect();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("main.go:4:4: Invalid parameter format: path parameter stale does not appear"));
+        assert!(problems[1].contains("main.go:4:4: Invalid parameter format: route /users/{id} has no @Param"));
+    }
+    #[test]
+    fn test_form_data_request_bodies() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Param avatar formData file true "avatar image"
+// @Param attachments formData []file false "more files"
+// @Param nickname formData string false "display name"
+// @Router /users/avatar [post]
+func uploadAvatar() {}
+
+// @Accept x-www-form-urlencoded
+// @Param username formData string true "user name"
+// @Param password formData string true "password"
+// @Router /login [post]
+func login() {}
+"#,
+        );
+
+        let upload = &operations.iter().find(|op| op.path == "/users/avatar").unwrap().operation;
+        assert!(upload.parameters.is_empty());
+        let body = upload.requestBody.as_ref().unwrap();
+        assert_eq!(body.required, Some(true));
+        let multipart = &body.content["multipart/form-data"];
+        let schema = multipart.schema.as_ref().unwrap();
+        assert_eq!(schema.required, Some(vec!["avatar".to_string()]));
+
+        let avatar = &schema.properties["avatar"];
+        assert_eq!(avatar.type_, Some(serde_json::Value::String("string".to_string())));
+        assert_eq!(avatar.format.as_deref(), Some("binary"));
+        assert_eq!(avatar.description.as_deref(), Some("avatar image"));
+        assert_eq!(
+            schema.properties["attachments"].items.as_ref().unwrap().format.as_deref(),
+            Some("binary")
+        );
+        assert_eq!(
+            multipart.encoding["avatar"].contentType.as_deref(),
+            Some("application/octet-stream")
+        );
+        assert!(!multipart.encoding.contains_key("nickname"));
+
+        let login = &operations.iter().find(|op| op.path == "/login").unwrap().operation;
+        let body = login.requestBody.as_ref().unwrap();
+        assert_eq!(body.content.len(), 1);
+        let form = &body.content["application/x-www-form-urlencoded"];
+        assert_eq!(form.schema.as_ref().unwrap().properties.len(), 2);
+        assert!(form.encoding.is_empty());
+    }
+    #[test]
+    fn test_param_attributes() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Param page query int false "page" minimum(1) maximum(100) default(1) multipleOf(1)
+// @Param name query string false "name" minLength(3) maxLength(64) Example(bob)
+// @Param status query string false "status" Enums(active, banned)
+// @Param level query integer false "level" Enums(1,2,3)
+// @Param ids query []int false "ids" collectionFormat(multi) Enums(1, 2)
+// @Param tags query []string false "tags" collectionFormat(csv) Default(a,b)
+// @Param flag query bool false "flag" Default(true) extensions(x-example=on,x-internal)
+// @Router /users [get]
+func listUsers() {}
+"#,
+        );
+
+        let params = &operations[0].operation.parameters;
+        let param = |name: &str| params.iter().find(|p| p.name == name).unwrap();
+        let schema = |name: &str| param(name).schema.clone().unwrap();
+
+        let page = schema("page");
+        assert_eq!(page.type_, Some(serde_json::json!("integer")));
+        assert_eq!(page.minimum, Some(1.0));
+        assert_eq!(page.maximum, Some(100.0));
+        assert_eq!(page.multipleOf, Some(1.0));
+        assert_eq!(page.default, Some(serde_json::json!(1)));
+
+        let name = schema("name");
+        assert_eq!(name.minLength, Some(3));
+        assert_eq!(name.maxLength, Some(64));
+        assert_eq!(param("name").example, Some(serde_json::json!("bob")));
+
+        assert_eq!(
+            schema("status").enum_values,
+            Some(vec![serde_json::json!("active"), serde_json::json!("banned")])
+        );
+        assert_eq!(
+            schema("level").enum_values,
+            Some(vec![serde_json::json!(1), serde_json::json!(2), serde_json::json!(3)])
+        );
+
+        let ids = schema("ids");
+        assert_eq!(ids.type_, Some(serde_json::json!("array")));
+        let items = ids.items.unwrap();
+        assert_eq!(items.type_, Some(serde_json::json!("integer")));
+        assert_eq!(items.enum_values, Some(vec![serde_json::json!(1), serde_json::json!(2)]));
+        assert_eq!(param("ids").style.as_deref(), Some("form"));
+        assert_eq!(param("ids").explode, Some(true));
+
+        assert_eq!(param("tags").explode, Some(false));
+        assert_eq!(schema("tags").default, Some(serde_json::json!(["a", "b"])));
+
+        let flag = schema("flag");
+        assert_eq!(flag.type_, Some(serde_json::json!("boolean")));
+        assert_eq!(flag.default, Some(serde_json::json!(true)));
+        assert_eq!(flag.extensions["x-example"], serde_json::json!("on"));
+        assert_eq!(flag.extensions["x-internal"], serde_json::json!(true));
+    }
+    #[test]
+    fn test_struct_parameter_expansion() {
+        let (operations, schemas) = parse_source(
+            r#"
+package main
+
+type Status string
+
+const (
+	StatusActive Status = "active"
+	StatusBanned Status = "banned"
+)
+
+type Paging struct {
+	// Page number, starting at 1
+	Page int `form:"page" binding:"min=1" example:"2"`
+}
+
+type ListFilter struct {
+	Paging
+	// Filter by name
+	Name     string  `form:"name" binding:"required,max=64"`
+	Status   *Status `form:"status"`
+	Internal string  `form:"-"`
+	Sort     string  `json:"sort"`
+}
+
+type RequestHeaders struct {
+	TraceID string `header:"X-Trace-ID"`
+}
+
+// @Param filter query ListFilter false "filters"
+// @Param headers header RequestHeaders false "headers"
+// @Success 200 {object} string
+// @Router /users [get]
+func listUsers() {}
+"#,
+        );
+
+        let params = &operations[0].operation.parameters;
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["page", "name", "status", "sort", "X-Trace-ID"]);
+        let param = |name: &str| params.iter().find(|p| p.name == name).unwrap();
+
+        let page = param("page");
+        assert_eq!(page.in_type, "query");
+        assert_eq!(page.description.as_deref(), Some("Page number, starting at 1"));
+        assert_eq!(page.required, Some(false));
+        assert_eq!(page.example, Some(serde_json::json!(2)));
+        assert_eq!(page.schema.as_ref().unwrap().minimum, Some(1.0));
+
+        let name = param("name");
+        assert_eq!(name.required, Some(true));
+        assert_eq!(name.schema.as_ref().unwrap().maxLength, Some(64));
+
+        assert_eq!(
+            param("status").schema.as_ref().unwrap().ref_.as_deref(),
+            Some("#/components/schemas/Status")
+        );
+        assert!(schemas.contains_key("Status"));
+        assert_eq!(param("X-Trace-ID").in_type, "header");
+    }
+
+    #[test]
+    fn test_fmt_annotations() {
+        let source = r#"package main
+
+// GetUser godoc
+// @summary Get a user
+// @param id path int true "User ID" minimum(1)
+// @Param   include_deleted   query bool false "Include soft-deleted users"
+// @success 200 {object} User "the user"
+// @router /users/{id} [get]
+func GetUser() {
+	x := 1 // @Param not an annotation
+	_ = x
+}
+"#;
+
+        let formatter = crate::formatter::Formatter::new();
+        let formatted = formatter.format_source(source, false).unwrap();
+        assert_eq!(
+            formatted,
+            "package main\n\n// GetUser godoc\n\
+             //\t@Summary\tGet a user\n\
+             //\t@Param\t\tid\t\t\t\tpath\t\tint\t\ttrue\t\"User ID\"\tminimum(1)\n\
+             //\t@Param\t\tinclude_deleted\tquery\t\tbool\tfalse\t\"Include soft-deleted users\"\n\
+             //\t@Success\t200\t\t\t\t{object}\tUser\t\"the user\"\n\
+             //\t@Router\t\t/users/{id}\t\t[get]\n\
+             func GetUser() {\n\tx := 1 // @Param not an annotation\n\t_ = x\n}\n"
+        );
+
+        // Formatting is idempotent
+        assert_eq!(formatter.format_source(&formatted, false).unwrap(), formatted);
+
+        // The check diff lists only the rewritten lines
+        let diff = crate::formatter::unified_diff("./main.go", source, &formatted);
+        assert!(diff.starts_with("--- a/main.go\n+++ b/main.go\n@@ -1,11 +1,11 @@\n"));
+        assert!(diff.contains("-// @summary Get a user\n"));
+        assert!(!diff.contains("-\tx := 1"));
+        assert!(crate::formatter::unified_diff("main.go", &formatted, &formatted).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostic_source_locations() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("main.go");
+        std::fs::write(
+            &file_path,
+            "package main\n\n// @title Demo\n//\t@securityDefinitions.oauth2 OAuth\nfunc main() {}\n",
+        )
+        .unwrap();
+
+        // The error points at the annotation, with its source line and a caret under it
+        let error = GoParser::new().parse_general_api_info(&file_path).unwrap_err();
+        let expected = format!(
+            "{}:4:4: Invalid security format: OAuth2 requires a flow type: oauth2\n  |\n\
+             4 | //\t@securityDefinitions.oauth2 OAuth\n  |   \t{}",
+            file_path.display(),
+            "^".repeat(33)
+        );
+        assert_eq!(error.to_string(), expected);
+
+        let location = crate::parser::SourceLocation {
+            file: "handlers/user.go".into(),
+            line: 12,
+            column: 4,
+            source_line: "// @Router /users [get]".to_string(),
+        };
+        let error = crate::parser::ParserError::RouterParseError("/users".to_string())
+            .at(Some(&location));
+        assert_eq!(
+            error.to_string(),
+            "handlers/user.go:12:4: Invalid router format: /users\n   |\n12 | // @Router /users [get]\n   |    ^^^^^^^^^^^^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_problems() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.go"),
+            r#"package main
+
+// GetUser godoc
+// @Summary Get a user
+// @Param id path
+// @Bogus x
+// @Success 200 {object} models.Missing
+// @Router /users/{id} [get]
+func GetUser() {}
+
+// @Success 200 {object} Profile
+// @Router /me [get]
+func GetMe() {}
+
+type Profile struct {
+	Team models.Team `json:"team"`
+}
+"#,
+        )
+        .unwrap();
+
+        let parser = GoParser::new();
+        let (operations, _) = parser
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+        assert_eq!(operations.len(), 2);
+
+        // Dropped annotations and unresolved models are collected in source order
+        let problems: Vec<String> = parser.take_problems().iter().map(|p| p.to_string()).collect();
+        assert_eq!(problems.len(), 5, "{:#?}", problems);
+        assert!(problems[0].contains("main.go:5:4: Invalid parameter format"));
+        assert!(problems[1].contains("main.go:6:4: Unknown annotation: @Bogus"));
+        // Without its @Param, the route's id parameter is only assumed to be a string
+        assert!(problems[2].contains("main.go:8:4: Invalid parameter format: route /users/{id} has no @Param"));
+        assert_eq!(
+            problems[3],
+            "Unresolved schema reference: no definition found for models.Missing"
+        );
+        // Unresolved field types are reported at the struct using them
+        assert!(problems[4].contains(
+            "main.go:15:6: Unresolved schema reference: no definition found for models.Team (used by Profile)\n"
+        ));
+        assert!(parser.take_problems().is_empty());
+
+        // Outside strict mode the generator fills missing references with placeholders
+        let generator = crate::generator::Generator::new(
+            crate::models::ParsedApiInfo::new(),
+            operations,
+            HashMap::new(),
+            "3.1.1".to_string(),
+        );
+        generator.generate(dir.path(), &["json".to_string()]).unwrap();
+        let spec: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("openapi.json")).unwrap())
+                .unwrap();
+        assert!(spec["components"]["schemas"]["Profile"].is_object());
+        assert!(spec["components"]["schemas"]["models.Missing"].is_object());
+    }
+
+    #[test]
+    fn test_lint_rules() {
+        use crate::linter::{LintConfig, Linter, Rule, Severity};
+
+        let (mut operations, mut schemas) = parse_source(
+            r#"package main
+
+// GetUser godoc
+// @Summary Get a user
+// @Description Fetch one user
+// @Tags users
+// @Security ApiKeyAuth
+// @ID getUser
+// @Success 200 {object} User
+// @Router /users/{id} [get]
+func GetUser() {}
+
+// @ID getUser
+// @Tags users
+// @Failure 404 {object} User
+// @Router /orderItems [get]
+func ListOrders() {}
+
+// @Summary Update settings
+// @Description Update user settings
+// @Failure 400 {object} User
+// @Router /user-settings [put]
+func UpdateSettings() {}
+
+type User struct {
+	ID int `json:"id"`
+}
+"#,
+        );
+        schemas.insert("Orphan".to_string(), Schema::default());
+        // The parser renames repeated IDs, so undo that as a hand-merged spec would
+        let orders = operations.iter_mut().find(|op| op.path == "/orderItems").unwrap();
+        assert_eq!(orders.operation.operationId.as_deref(), Some("getUser_2"));
+        orders.operation.operationId = Some("getUser".to_string());
+
+        let mut api_info = crate::models::ParsedApiInfo::new();
+        api_info.security_definitions.insert(
+            "BasicAuth".to_string(),
+            crate::models::SecurityScheme {
+                type_: "http".to_string(),
+                description: None,
+                name: None,
+                in_type: None,
+                scheme: Some("basic".to_string()),
+                bearerFormat: None,
+                flows: None,
+                openIdConnectUrl: None,
+                extensions: HashMap::new(),
+            },
+        );
+
+        let findings = Linter::default().lint(&api_info, &operations, &schemas);
+        let found: Vec<(Rule, Severity, &str)> = findings
+            .iter()
+            .map(|f| (f.rule, f.severity, f.target.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Rule::MissingSummary, Severity::Warning, "GET /orderItems"),
+                (Rule::MissingDescription, Severity::Info, "GET /orderItems"),
+                (Rule::NoErrorResponses, Severity::Warning, "GET /users/{id}"),
+                (Rule::DuplicateOperationId, Severity::Error, "GET /orderItems"),
+                (Rule::UndefinedSecurityScheme, Severity::Error, "GET /users/{id}"),
+                (Rule::UndeclaredTag, Severity::Warning, "tag users"),
+                (Rule::UnusedSchema, Severity::Warning, "schema Orphan"),
+                (Rule::InconsistentPathCasing, Severity::Warning, "/orderItems"),
+            ]
+        );
+        assert_eq!(
+            findings[3].message,
+            "operationId getUser is already used by GET /users/{id}"
+        );
+
+        // Rules can be switched off or given another severity
+        let config = LintConfig::default()
+            .with_settings("missing-description=off, unused-schema=error")
+            .unwrap();
+        let findings = Linter::new(config).lint(&api_info, &operations, &schemas);
+        assert!(findings.iter().all(|f| f.rule != Rule::MissingDescription));
+        let unused = findings.iter().find(|f| f.rule == Rule::UnusedSchema).unwrap();
+        assert_eq!(unused.severity, Severity::Error);
+        assert!(LintConfig::default().with_settings("no-such-rule=off").is_err());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&crate::linter::format_json(&findings).unwrap()).unwrap();
+        assert_eq!(json[0]["rule"], "missing-summary");
+        assert_eq!(json[0]["severity"], "warning");
+        assert!(crate::linter::format_text(&findings)
+            .ends_with("3 error(s), 4 warning(s), 0 info\n"));
+    }
+
+    #[test]
+    fn test_oas30_output() {
+        use crate::models::Schema;
+
+        let source = r#"
+package main
+
+import "database/sql"
+
+type Team struct {
+	Name string `json:"name"`
+}
+
+type Item struct {
+	Nickname sql.NullString `json:"nickname"`
+	Quantity int            `json:"quantity" binding:"gt=0"`
+	// The owning team
+	Team   Team    `json:"team"`
+	Parent *Team   `json:"parent"`
+	Note   *string `json:"note"`
+	Hidden *string `json:"hidden,omitempty"`
+}
+
+// @Summary An item was added
+// @Param item body Item true "the new item"
+// @Success 200 {string} string "received"
+// @Webhook newItem [post]
+func newItemHook() {}
+
+// @Success 200 {object} Item
+// @Router /items [get]
+func listItems() {}
+"#;
+        let generate = |version: &str| {
+            let (operations, mut schemas) = parse_source(source);
+            let mut tuple = Schema {
+                type_: Some(serde_json::json!(["array", "string", "null"])),
+                prefixItems: Some(vec![Schema {
+                    type_: Some(serde_json::json!("string")),
+                    ..Default::default()
+                }]),
+                const_: Some(serde_json::json!(["a"])),
+                ..Default::default()
+            };
+            tuple.defs_.insert("Inner".to_string(), Box::new(Schema::default()));
+            schemas.insert("Tuple".to_string(), tuple);
+
+            let dir = tempdir().unwrap();
+            crate::generator::Generator::new(
+                crate::models::ParsedApiInfo::new(),
+                operations,
+                schemas,
+                version.to_string(),
+            )
+            .generate(dir.path(), &["json".to_string()])
+            .unwrap();
+            serde_json::from_str::<serde_json::Value>(
+                &std::fs::read_to_string(dir.path().join("openapi.json")).unwrap(),
+            )
+            .unwrap()
+        };
+
+        let spec = generate("3.0.3");
+        assert_eq!(spec["openapi"], "3.0.3");
+        assert!(spec.get("webhooks").is_none());
+        assert!(spec.get("jsonSchemaDialect").is_none());
+
+        let item = &spec["components"]["schemas"]["Item"]["properties"];
+        assert_eq!(item["nickname"]["type"], "string");
+        assert_eq!(item["nickname"]["nullable"], true);
+        assert_eq!(item["quantity"]["minimum"], 0.0);
+        assert_eq!(item["quantity"]["exclusiveMinimum"], true);
+
+        // Pointer fields are nullable unless omitempty leaves them out
+        assert_eq!(item["note"]["type"], "string");
+        assert_eq!(item["note"]["nullable"], true);
+        assert!(item["hidden"].get("nullable").is_none());
+        assert_eq!(item["parent"]["nullable"], true);
+        assert_eq!(item["parent"]["allOf"][0]["$ref"], "#/components/schemas/Team");
+        assert!(item["parent"].get("anyOf").is_none());
+
+        // A reference with sibling keywords moves into allOf
+        assert_eq!(item["team"]["description"], "The owning team");
+        assert_eq!(item["team"]["allOf"][0]["$ref"], "#/components/schemas/Team");
+        assert!(item["team"].get("$ref").is_none());
+
+        let tuple = &spec["components"]["schemas"]["Tuple"];
+        assert!(tuple.get("type").is_none());
+        assert_eq!(tuple["nullable"], true);
+        assert_eq!(tuple["allOf"][0]["anyOf"][1]["type"], "string");
+        assert_eq!(tuple["enum"], serde_json::json!([["a"]]));
+        assert!(tuple.get("const").is_none());
+        assert!(tuple.get("prefixItems").is_none());
+        assert!(tuple.get("$defs").is_none());
+        assert_eq!(tuple["items"]["anyOf"][0]["type"], "string");
+
+        // OpenAPI 3.1 output keeps the JSON Schema 2020-12 forms
+        let spec = generate("3.1.1");
+        assert!(spec["webhooks"]["newItem"].is_object());
+        let item = &spec["components"]["schemas"]["Item"]["properties"];
+        assert_eq!(item["nickname"]["type"], serde_json::json!(["string", "null"]));
+        assert_eq!(item["quantity"]["exclusiveMinimum"], 0.0);
+        assert_eq!(spec["components"]["schemas"]["Tuple"]["const"], serde_json::json!(["a"]));
+
+        // Pointer fields stay nullable the JSON Schema way
+        assert_eq!(item["note"]["type"], serde_json::json!(["string", "null"]));
+        assert!(item["note"].get("nullable").is_none());
+        assert_eq!(item["hidden"]["type"], "string");
+        assert_eq!(
+            item["parent"]["anyOf"],
+            serde_json::json!([{ "$ref": "#/components/schemas/Team" }, { "type": "null" }])
+        );
+        assert!(item["parent"].get("nullable").is_none());
+        assert_eq!(item["team"]["$ref"], "#/components/schemas/Team");
+        assert_eq!(item["team"]["description"], "The owning team");
+    }
+
+    #[test]
+    fn test_fixed_size_arrays() {
+        let (_operations, schemas) = parse_source(
+            r#"
+package main
+
+type Checksum struct {
+    Digest [4]byte    `json:"digest"`
+    Raw    []byte     `json:"raw"`
+    Pair   [2]string  `json:"pair"`
+}
+
+// @Success 200 {object} Checksum
+// @Router /checksum [get]
+func getChecksum() {}
+"#,
+        );
+
+        let checksum = &schemas["Checksum"];
+        let digest = &checksum.properties["digest"];
+        assert_eq!(digest.type_, Some(serde_json::json!("array")));
+        assert_eq!(digest.items.as_ref().unwrap().type_, Some(serde_json::json!("integer")));
+        assert_eq!((digest.minItems, digest.maxItems), (Some(4), Some(4)));
+        assert!(digest.ref_.is_none());
+        assert_eq!(checksum.properties["raw"].format, Some("byte".to_string()));
+        assert_eq!(checksum.properties["pair"].maxItems, Some(2));
+        assert!(!schemas.keys().any(|name| name.contains('[')));
+    }
+
+    #[test]
+    fn test_fmt_scope() {
+        let source = "// @title  Demo API\n//\t@description.markdown\n\
+                      // @securityDefinitions.apikey  ApiKeyAuth\n// @in header\n\
+                      package main\n\n\
+                      // @Summary  List users\n// @Description Tabs\tstay as\twritten\n\
+                      // @see the handbook\n// @Router  /users [get]\n\
+                      func listUsers() {\n\t// @Param not an annotation\n}\n";
+        let formatter = crate::formatter::Formatter::new();
+
+        // Function doc comments are formatted in every file, but prose and bodies are not
+        let formatted = formatter.format_source(source, false).unwrap();
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[0], "// @title  Demo API");
+        assert_eq!(lines[6], "//\t@Summary\t\tList users");
+        assert_eq!(lines[7], "//\t@Description\tTabs\tstay as\twritten");
+        assert_eq!(lines[8], "// @see the handbook");
+        assert_eq!(lines[9], "//\t@Router\t/users\t[get]");
+        assert_eq!(lines[11], "\t// @Param not an annotation");
+
+        // The package doc of the general info file holds the general API info
+        let formatted = formatter.format_source(source, true).unwrap();
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[0], "//\t@title\tDemo API");
+        assert_eq!(lines[2], "//\t@securityDefinitions.apikey\tApiKeyAuth");
+        assert_eq!(lines[3], "//\t@in\t\t\t\t\t\t\theader");
+    }
+
+    #[test]
+    fn test_annotation_problem_locations() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.go"),
+            r#"package main
+
+// @title Demo
+// @externalDocs.link https://example.com
+func main() {}
+
+// @Param id query int false "id" maximun(10)
+// @Success 200 {string} string "ok"
+// @Header 404 {string} X-Request-ID "request id"
+// @Deprecated.until 2030-01-01
+// @Router /users [get]
+func ListUsers() {}
+"#,
+        )
+        .unwrap();
+
+        let parser = GoParser::new();
+        parser.parse_general_api_info(dir.path().join("main.go")).unwrap();
+        parser
+            .parse_operations(&[dir.path()], &[] as &[&str], dir.path())
+            .unwrap();
+
+        let problems: Vec<String> = parser.take_problems().iter().map(|p| p.to_string()).collect();
+        assert_eq!(problems.len(), 4, "{:#?}", problems);
+        assert!(problems[0].contains("main.go:4:4: Unknown attribute: @externalDocs.link\n"));
+        assert!(problems[1].contains("main.go:7:4: Unknown attribute: maximun(10) on parameter id\n"));
+        assert!(problems[2].contains("main.go:10:4: Unknown attribute: @Deprecated.until\n"));
+        // Headers are matched with responses once the whole handler is read
+        assert!(problems[3].contains(
+            "main.go:9:4: Invalid header format: Header X-Request-ID refers to undeclared response 404 on /users\n"
+        ));
+        assert!(problems[3].ends_with(&format!("  |    {}", "^".repeat(46))));
+    }
+
+    #[test]
+    fn test_strict_security_definitions() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("main.go");
+        std::fs::write(
+            &file_path,
+            r#"package main
+
+// @title Demo
+// @version 1.0
+// @securityDefinitions.apikey ApiKeyAuth
+// @in header
+// @name Authorization
+// @description Session token
+// @securityDefinitions.oauth2.clientCredentials OAuth2
+// @tokenUrl https://example.com/oauth/token
+// @scope.admin Grants admin access
+// @x-internal true
+// @host example.com
+func main() {}
+"#,
+        )
+        .unwrap();
+
+        let parser = GoParser::new();
+        let api_info = parser.parse_general_api_info(&file_path).unwrap();
+        // swag's security definition lines are not unknown annotations
+        assert!(parser.take_problems().is_empty());
+
+        let api_key = &api_info.security_definitions["ApiKeyAuth"];
+        assert_eq!(api_key.type_, "apiKey");
+        assert_eq!(api_key.in_type.as_deref(), Some("header"));
+        assert_eq!(api_key.name.as_deref(), Some("Authorization"));
+        assert_eq!(api_key.description.as_deref(), Some("Session token"));
+
+        let oauth = &api_info.security_definitions["OAuth2"];
+        let flow = oauth.flows.as_ref().unwrap().clientCredentials.as_ref().unwrap();
+        assert_eq!(flow.tokenUrl.as_deref(), Some("https://example.com/oauth/token"));
+        assert_eq!(flow.scopes["admin"], "Grants admin access");
+        assert_eq!(oauth.extensions["x-internal"], serde_json::json!(true));
+        assert_eq!(api_info.info.description, None);
+
+        // Attribute lines without a definition above them are problems
+        std::fs::write(&file_path, "package main\n\n// @title Demo\n// @in header\nfunc main() {}\n")
+            .unwrap();
+        parser.parse_general_api_info(&file_path).unwrap();
+        let problems = parser.take_problems();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0]
+            .to_string()
+            .contains("main.go:4:4: Invalid security format: @in must follow a @securityDefinitions line"));
+    }
+
+    #[test]
+    fn test_response_code_lists() {
+        let (operations, _schemas) = parse_source(
+            r#"
+package main
+
+// @Success 200,201 {object} string "stored"
+// @Failure 400,404 {object} string
+// @Header 201 {string} X-Request-ID "trace id"
+// @Router /items [put]
+func putItem() {}
+"#,
+        );
+
+        let responses = &operations[0].operation.responses;
+        let mut codes: Vec<&str> = responses.keys().map(String::as_str).collect();
+        codes.sort_unstable();
+        assert_eq!(codes, ["200", "201", "400", "404"]);
+        assert_eq!(responses["201"].code, "201");
+        assert_eq!(responses["200"].description, "stored");
+        assert!(responses["201"].headers.contains_key("X-Request-ID"));
+        assert!(!responses["200"].headers.contains_key("X-Request-ID"));
     }
 }
@@ -0,0 +1,549 @@
+//! API design rules checked by `swaggo-rust lint`
+//!
+//! The linter runs over the parsed operations and schemas, so findings refer to
+//! operations (`GET /users/{id}`), schemas and tags rather than source lines.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::models::{Operation, ParsedApiInfo, ParsedOperation, Schema};
+use crate::parser::COMMON_SCHEMA_NAMES;
+
+#[derive(Error, Debug)]
+pub enum LintError {
+    #[error("Unknown lint rule: {0}")]
+    UnknownRule(String),
+
+    #[error("Unknown severity {0}: expected off, info, warning or error")]
+    UnknownSeverity(String),
+
+    #[error("Invalid rule setting {0}: expected rule=severity")]
+    InvalidSetting(String),
+}
+
+/// How a rule's findings are reported; `Off` disables the rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl FromStr for Severity {
+    type Err = LintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "info" => Ok(Self::Info),
+            "warning" | "warn" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => Err(LintError::UnknownSeverity(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    MissingSummary,
+    MissingDescription,
+    NoErrorResponses,
+    DuplicateOperationId,
+    UndefinedSecurityScheme,
+    UndeclaredTag,
+    UnusedSchema,
+    InconsistentPathCasing,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 8] = [
+        Rule::MissingSummary,
+        Rule::MissingDescription,
+        Rule::NoErrorResponses,
+        Rule::DuplicateOperationId,
+        Rule::UndefinedSecurityScheme,
+        Rule::UndeclaredTag,
+        Rule::UnusedSchema,
+        Rule::InconsistentPathCasing,
+    ];
+
+    /// The name used on the command line and in reports
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MissingSummary => "missing-summary",
+            Self::MissingDescription => "missing-description",
+            Self::NoErrorResponses => "no-error-responses",
+            Self::DuplicateOperationId => "duplicate-operation-id",
+            Self::UndefinedSecurityScheme => "undefined-security-scheme",
+            Self::UndeclaredTag => "undeclared-tag",
+            Self::UnusedSchema => "unused-schema",
+            Self::InconsistentPathCasing => "inconsistent-path-casing",
+        }
+    }
+
+    fn default_severity(self) -> Severity {
+        match self {
+            Self::DuplicateOperationId | Self::UndefinedSecurityScheme => Severity::Error,
+            Self::MissingDescription => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = LintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| LintError::UnknownRule(s.to_string()))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Severity of each rule
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    severities: HashMap<Rule, Severity>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            severities: Rule::ALL
+                .into_iter()
+                .map(|rule| (rule, rule.default_severity()))
+                .collect(),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn with_severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.severities.insert(rule, severity);
+        self
+    }
+
+    /// Apply comma separated `rule=severity` settings, e.g. `unused-schema=off,missing-description=warning`
+    pub fn with_settings(mut self, settings: &str) -> Result<Self, LintError> {
+        for setting in settings.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (rule, severity) = setting
+                .split_once('=')
+                .ok_or_else(|| LintError::InvalidSetting(setting.to_string()))?;
+            self = self.with_severity(rule.trim().parse()?, severity.trim().parse()?);
+        }
+        Ok(self)
+    }
+
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities.get(&rule).copied().unwrap_or(Severity::Off)
+    }
+}
+
+/// A problem found by a rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// The operation (`GET /users`), path, schema or tag the finding is about
+    pub target: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}] {}: {}", self.severity, self.rule, self.target, self.message)
+    }
+}
+
+/// Checks parsed operations and schemas against the configured rules
+#[derive(Debug, Default)]
+pub struct Linter {
+    config: LintConfig,
+}
+
+impl Linter {
+    pub fn new(config: LintConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run every enabled rule; findings are grouped by rule, in the order of [`Rule::ALL`]
+    pub fn lint(
+        &self,
+        api_info: &ParsedApiInfo,
+        operations: &[ParsedOperation],
+        schemas: &HashMap<String, Schema>,
+    ) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for rule in Rule::ALL {
+            let severity = self.config.severity(rule);
+            if severity == Severity::Off {
+                continue;
+            }
+
+            let problems = match rule {
+                Rule::MissingSummary => check_operations(operations, |op| {
+                    is_blank(&op.summary).then(|| "operation has no summary".to_string())
+                }),
+                Rule::MissingDescription => check_operations(operations, |op| {
+                    is_blank(&op.description).then(|| "operation has no description".to_string())
+                }),
+                Rule::NoErrorResponses => check_operations(operations, |op| {
+                    let has_error_response = op.responses.keys().any(|code| {
+                        code == "default" || code.starts_with('4') || code.starts_with('5')
+                    });
+                    (!has_error_response)
+                        .then(|| "operation documents no 4XX, 5XX or default response".to_string())
+                }),
+                Rule::DuplicateOperationId => duplicate_operation_ids(operations),
+                Rule::UndefinedSecurityScheme => undefined_security_schemes(api_info, operations),
+                Rule::UndeclaredTag => undeclared_tags(api_info, operations),
+                Rule::UnusedSchema => unused_schemas(operations, schemas),
+                Rule::InconsistentPathCasing => inconsistent_path_casing(operations),
+            };
+
+            findings.extend(problems.into_iter().map(|(target, message)| Finding {
+                rule,
+                severity,
+                target,
+                message,
+            }));
+        }
+        findings
+    }
+}
+
+/// Findings as text, one per line, followed by a count by severity
+pub fn format_text(findings: &[Finding]) -> String {
+    let mut text: String = findings.iter().map(|finding| format!("{}\n", finding)).collect();
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    text.push_str(&format!(
+        "{} error(s), {} warning(s), {} info\n",
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Info)
+    ));
+    text
+}
+
+/// Findings as a JSON array
+pub fn format_json(findings: &[Finding]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(findings)
+}
+
+// `GET /users/{id}`, or `webhook newPet (POST)`
+fn operation_target(operation: &ParsedOperation) -> String {
+    if operation.webhook {
+        format!("webhook {} ({})", operation.path, operation.method.to_uppercase())
+    } else {
+        format!("{} {}", operation.method.to_uppercase(), operation.path)
+    }
+}
+
+fn is_blank(text: &Option<String>) -> bool {
+    text.as_deref().is_none_or(|text| text.trim().is_empty())
+}
+
+// Apply a per-operation check, keyed by operation
+fn check_operations(
+    operations: &[ParsedOperation],
+    check: impl Fn(&Operation) -> Option<String>,
+) -> Vec<(String, String)> {
+    operations
+        .iter()
+        .filter_map(|op| check(&op.operation).map(|message| (operation_target(op), message)))
+        .collect()
+}
+
+fn duplicate_operation_ids(operations: &[ParsedOperation]) -> Vec<(String, String)> {
+    let mut first_use: HashMap<&str, String> = HashMap::new();
+    let mut problems = Vec::new();
+    for op in operations {
+        let Some(id) = op.operation.operationId.as_deref() else {
+            continue;
+        };
+        match first_use.get(id) {
+            Some(first) => problems.push((
+                operation_target(op),
+                format!("operationId {} is already used by {}", id, first),
+            )),
+            None => {
+                first_use.insert(id, operation_target(op));
+            }
+        }
+    }
+    problems
+}
+
+fn undefined_security_schemes(
+    api_info: &ParsedApiInfo,
+    operations: &[ParsedOperation],
+) -> Vec<(String, String)> {
+    let undefined = |requirements: &[HashMap<String, Vec<String>>]| {
+        let mut names: Vec<String> = requirements
+            .iter()
+            .flat_map(|requirement| requirement.keys())
+            .filter(|name| !api_info.security_definitions.contains_key(*name))
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    };
+
+    let mut problems = Vec::new();
+    for name in undefined(&api_info.security) {
+        problems.push((
+            "API".to_string(),
+            format!("security scheme {} is not defined in securityDefinitions", name),
+        ));
+    }
+    for op in operations {
+        for name in undefined(&op.operation.security) {
+            problems.push((
+                operation_target(op),
+                format!("security scheme {} is not defined in securityDefinitions", name),
+            ));
+        }
+    }
+    problems
+}
+
+fn undeclared_tags(
+    api_info: &ParsedApiInfo,
+    operations: &[ParsedOperation],
+) -> Vec<(String, String)> {
+    let declared: HashSet<&str> = api_info.tags.iter().map(|tag| tag.name.as_str()).collect();
+
+    // Tags in order of first use, with the number of operations using them
+    let mut used: Vec<(&str, usize)> = Vec::new();
+    for tag in operations.iter().flat_map(|op| &op.operation.tags) {
+        match used.iter_mut().find(|(name, _)| name == tag) {
+            Some((_, count)) => *count += 1,
+            None => used.push((tag, 1)),
+        }
+    }
+
+    used.into_iter()
+        .filter(|(name, _)| !declared.contains(name))
+        .map(|(name, count)| {
+            (
+                format!("tag {}", name),
+                format!("used by {} operation(s) but not declared with @tag.name", count),
+            )
+        })
+        .collect()
+}
+
+fn unused_schemas(
+    operations: &[ParsedOperation],
+    schemas: &HashMap<String, Schema>,
+) -> Vec<(String, String)> {
+    // Models are known both by their qualified and their simple name, e.g.
+    // `models.User` and `User`; using either one uses both
+    let simple_name = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+
+    let mut pending = Vec::new();
+    for op in operations {
+        collect_operation_refs(&op.operation, &mut pending);
+    }
+
+    let mut used = HashSet::new();
+    while let Some(name) = pending.pop() {
+        if !used.insert(simple_name(&name)) {
+            continue;
+        }
+        for (schema_name, schema) in schemas {
+            if simple_name(schema_name) == simple_name(&name) {
+                collect_schema_refs(schema, &mut pending);
+            }
+        }
+    }
+
+    let mut unused: Vec<&String> = schemas
+        .keys()
+        .filter(|name| !used.contains(&simple_name(name)))
+        .filter(|name| !COMMON_SCHEMA_NAMES.contains(&name.as_str()))
+        .collect();
+    unused.sort();
+    unused
+        .into_iter()
+        .map(|name| {
+            (
+                format!("schema {}", name),
+                "component schema is never referenced".to_string(),
+            )
+        })
+        .collect()
+}
+
+// Schema names referenced by an operation and its callbacks
+fn collect_operation_refs(operation: &Operation, refs: &mut Vec<String>) {
+    let parameter_schemas = operation.parameters.iter().filter_map(|p| p.schema.as_ref());
+    let body_schemas = operation
+        .requestBody
+        .iter()
+        .flat_map(|body| body.content.values())
+        .filter_map(|media_type| media_type.schema.as_ref());
+    let response_schemas = operation
+        .responses
+        .values()
+        .flat_map(|response| response.content.values())
+        .filter_map(|media_type| media_type.schema.as_ref());
+    for schema in parameter_schemas.chain(body_schemas).chain(response_schemas) {
+        collect_schema_refs(schema, refs);
+    }
+
+    for path_item in operation.callbacks.values().flat_map(|callback| callback.values()) {
+        for callback_operation in path_item.operations() {
+            collect_operation_refs(callback_operation, refs);
+        }
+    }
+}
+
+fn collect_schema_refs(schema: &Schema, refs: &mut Vec<String>) {
+    if let Some(ref_) = &schema.ref_ {
+        refs.push(ref_.rsplit('/').next().unwrap_or(ref_).to_string());
+    }
+
+    let nested = schema
+        .items
+        .iter()
+        .chain(schema.properties.values())
+        .chain(schema.not.iter())
+        .map(|schema| schema.as_ref());
+    let composed = [&schema.allOf, &schema.anyOf, &schema.oneOf]
+        .into_iter()
+        .flatten()
+        .flatten();
+    for schema in nested.chain(composed) {
+        collect_schema_refs(schema, refs);
+    }
+
+    if let Some(additional) = &schema.additionalProperties {
+        if let Ok(additional) = serde_json::from_value::<Schema>(additional.clone()) {
+            collect_schema_refs(&additional, refs);
+        }
+    }
+}
+
+// Word separation style of a multi-word path segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentCase {
+    Kebab,
+    Snake,
+    Camel,
+}
+
+impl SegmentCase {
+    fn of(segment: &str) -> Option<Self> {
+        if segment.contains('-') {
+            Some(Self::Kebab)
+        } else if segment.contains('_') {
+            Some(Self::Snake)
+        } else if segment.starts_with(|c: char| c.is_ascii_lowercase())
+            && segment.contains(|c: char| c.is_ascii_uppercase())
+        {
+            Some(Self::Camel)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Kebab => "kebab-case",
+            Self::Snake => "snake_case",
+            Self::Camel => "camelCase",
+        }
+    }
+}
+
+// Paths whose literal segments break the casing most paths follow: the most
+// common word separation style, and lowercase first letters when most are lowercase
+fn inconsistent_path_casing(operations: &[ParsedOperation]) -> Vec<(String, String)> {
+    let mut paths: Vec<&str> = operations
+        .iter()
+        .filter(|op| !op.webhook)
+        .map(|op| op.path.as_str())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let literal_segments = |path: &str| -> Vec<String> {
+        path.split('/')
+            .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+            .map(str::to_string)
+            .collect()
+    };
+
+    let all_segments: Vec<String> = paths.iter().flat_map(|path| literal_segments(path)).collect();
+    let cases: Vec<SegmentCase> = all_segments.iter().filter_map(|s| SegmentCase::of(s)).collect();
+    // `max_by_key` keeps the last maximum, so ties go to kebab-case, then snake_case
+    let convention = [SegmentCase::Camel, SegmentCase::Snake, SegmentCase::Kebab]
+        .into_iter()
+        .map(|case| (case, cases.iter().filter(|c| **c == case).count()))
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map(|(case, _)| case);
+
+    let starts_with = |predicate: fn(&char) -> bool| {
+        all_segments
+            .iter()
+            .filter(|s| s.chars().next().is_some_and(|c| predicate(&c)))
+            .count()
+    };
+    let mostly_lowercase =
+        starts_with(char::is_ascii_lowercase) > starts_with(char::is_ascii_uppercase);
+
+    let mut problems = Vec::new();
+    for path in paths {
+        let message = literal_segments(path).into_iter().find_map(|segment| {
+            if let (Some(case), Some(convention)) = (SegmentCase::of(&segment), convention) {
+                if case != convention {
+                    return Some(format!(
+                        "segment {} is {} while most paths use {}",
+                        segment,
+                        case.name(),
+                        convention.name()
+                    ));
+                }
+            }
+            if mostly_lowercase && segment.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return Some(format!(
+                    "segment {} starts with an uppercase letter while most paths are lowercase",
+                    segment
+                ));
+            }
+            None
+        });
+        if let Some(message) = message {
+            problems.push((path.to_string(), message));
+        }
+    }
+    problems
+}
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use env_logger::Env;
-use log::{debug, info};
-use std::path::Path;
+use log::{debug, info, warn};
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+mod formatter;
 mod generator;
+mod go_ast;
+mod linter;
 mod models;
 mod parser;
 
@@ -41,7 +44,7 @@ enum Commands {
         #[arg(long = "ot", default_value = "go,json,yaml,ui")]
         output_types: String,
 
-        /// OpenAPI version (3.0.0, 3.1.0, 3.1.1)
+        /// OpenAPI version (3.0.x output is converted to 3.0 semantics)
         #[arg(long = "oas", default_value = "3.1.1")]
         openapi_version: String,
 
@@ -52,6 +55,18 @@ enum Commands {
         /// Directories to exclude, comma separated
         #[arg(long = "exclude-dir")]
         exclude_dir: Option<String>,
+
+        /// Emit embedded structs as allOf compositions instead of flattening their fields
+        #[arg(long = "embedded-all-of")]
+        embedded_all_of: bool,
+
+        /// File with type overrides (replace/skip directives)
+        #[arg(long = "overrides-file", default_value = ".swaggo")]
+        overrides_file: String,
+
+        /// Fail without generating anything if an annotation is dropped or a schema reference is unresolved
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Format OpenAPI comments
@@ -63,6 +78,41 @@ enum Commands {
         /// Go file path in which 'general API Info' is written
         #[arg(short = 'g', long)]
         general_info: Option<String>,
+
+        /// Directories to exclude, comma separated
+        #[arg(long = "exclude-dir")]
+        exclude_dir: Option<String>,
+
+        /// Print a diff of unformatted files instead of rewriting them, and fail if there are any
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Check the API against design rules
+    Lint {
+        /// Go file path in which 'general API Info' is written
+        #[arg(short = 'g', long)]
+        general_info: Option<String>,
+
+        /// Directories to parse, comma separated
+        #[arg(short, long, default_value = "./")]
+        dir: String,
+
+        /// Directories to exclude, comma separated
+        #[arg(long = "exclude-dir")]
+        exclude_dir: Option<String>,
+
+        /// File with type overrides (replace/skip directives)
+        #[arg(long = "overrides-file", default_value = ".swaggo")]
+        overrides_file: String,
+
+        /// Rule severities, comma separated, e.g. unused-schema=off,missing-description=error
+        #[arg(long)]
+        rules: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(long, default_value = "text")]
+        format: String,
     },
 }
 
@@ -84,6 +134,9 @@ fn main() -> Result<()> {
             openapi_version,
             max_file_size,
             exclude_dir,
+            embedded_all_of,
+            overrides_file,
+            strict,
         } => {
             info!("Initializing OpenAPI docs");
 
@@ -104,8 +157,18 @@ fn main() -> Result<()> {
                 max_file_size, max_file_size_bytes
             );
 
+            // Load project type overrides if present
+            let type_overrides = if Path::new(overrides_file).exists() {
+                debug!("Using type overrides from {}", overrides_file);
+                parser::TypeOverrides::from_file(overrides_file)?
+            } else {
+                parser::TypeOverrides::default()
+            };
+
             // Create the parser
-            let parser = parser::GoParser::new();
+            let parser = parser::GoParser::new()
+                .with_embedded_all_of(*embedded_all_of)
+                .with_type_overrides(type_overrides);
 
             // Find the general API info file if not provided
             let found_general_info = match general_info {
@@ -126,26 +189,7 @@ fn main() -> Result<()> {
             debug!("Parsed API info: {:?}", api_info);
 
             // Get the base directory for resolving imports
-            // First try to find the directory containing go.mod
-            let base_dir = {
-                let found_general_info_path = Path::new(&found_general_info);
-                let general_info_dir = found_general_info_path.parent().unwrap_or(Path::new("."));
-
-                let mut current_dir = general_info_dir.to_path_buf();
-                let mut go_mod_dir = current_dir.clone();
-
-                // Try to locate go.mod file by walking up directories
-                while current_dir.parent().is_some() {
-                    let go_mod = current_dir.join("go.mod");
-                    if go_mod.exists() {
-                        go_mod_dir = current_dir.clone();
-                        break;
-                    }
-                    current_dir = current_dir.parent().unwrap().to_path_buf();
-                }
-
-                go_mod_dir
-            };
+            let base_dir = find_base_dir(&found_general_info);
 
             debug!("Using base directory for imports: {:?}", base_dir);
 
@@ -163,14 +207,39 @@ fn main() -> Result<()> {
                 max_file_size_bytes,
                 openapi_version.to_string(),
             );
+
+            // In strict mode, broken annotations and references stop generation
+            if *strict {
+                let problems: Vec<String> =
+                    parser.take_problems().iter().map(ToString::to_string).collect();
+                if !problems.is_empty() {
+                    for problem in &problems {
+                        eprintln!("{}\n", problem);
+                    }
+                    anyhow::bail!(
+                        "Strict mode found {} problem(s); no documentation was generated",
+                        problems.len()
+                    );
+                }
+            }
+
             generator.generate(output, &output_types)?;
 
             info!("OpenAPI documentation generated successfully");
         }
-        Commands::Fmt { general_info, dir } => {
+        Commands::Fmt {
+            general_info,
+            dir,
+            exclude_dir,
+            check,
+        } => {
             info!("Formatting OpenAPI comments");
 
             let dirs: Vec<String> = dir.split(',').map(|s| s.trim().to_string()).collect();
+            let excluded_dirs: Vec<String> = exclude_dir
+                .as_ref()
+                .map(|ed| ed.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
 
             // Find the general API info file if not provided
             let found_general_info = match general_info {
@@ -181,14 +250,129 @@ fn main() -> Result<()> {
             debug!("General info file: {}", found_general_info);
             debug!("Directories to format: {}", dir);
 
-            // TODO: Implement formatting
-            info!("Formatting is not yet implemented");
+            // The general info file is formatted too, even when it lives outside the directories
+            let parser = parser::GoParser::new();
+            let mut files = Vec::new();
+            for dir in &dirs {
+                parser.collect_go_files_recursively(Path::new(dir), &excluded_dirs, &mut files);
+            }
+            let general_info_path = Path::new(&found_general_info).to_path_buf();
+            // Only the general info file has its package doc comment formatted
+            let general_info_file = general_info_path.canonicalize().ok();
+            if general_info_path.exists() && !files.contains(&general_info_path) {
+                files.push(general_info_path);
+            }
+
+            let formatter = formatter::Formatter::new();
+            let mut unformatted = 0;
+            for file in &files {
+                let source = std::fs::read_to_string(file)?;
+                let is_general_info =
+                    general_info_file.is_some() && file.canonicalize().ok() == general_info_file;
+                let formatted = match formatter.format_source(&source, is_general_info) {
+                    Ok(formatted) => formatted,
+                    Err(e) => {
+                        warn!("Skipping {}: {}", file.display(), e);
+                        continue;
+                    }
+                };
+                if formatted == source {
+                    continue;
+                }
+
+                unformatted += 1;
+                if *check {
+                    print!(
+                        "{}",
+                        formatter::unified_diff(&file.to_string_lossy(), &source, &formatted)
+                    );
+                } else {
+                    std::fs::write(file, formatted)?;
+                    info!("Formatted {}", file.display());
+                }
+            }
+
+            if *check && unformatted > 0 {
+                anyhow::bail!("{} file(s) are not formatted", unformatted);
+            }
+        }
+        Commands::Lint {
+            general_info,
+            dir,
+            exclude_dir,
+            overrides_file,
+            rules,
+            format,
+        } => {
+            let dirs: Vec<String> = dir.split(',').map(|s| s.trim().to_string()).collect();
+            let excluded_dirs: Vec<String> = exclude_dir
+                .as_ref()
+                .map(|ed| ed.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+
+            let config = match rules {
+                Some(rules) => linter::LintConfig::default().with_settings(rules)?,
+                None => linter::LintConfig::default(),
+            };
+
+            let found_general_info = match general_info {
+                Some(path) => path.clone(),
+                None => find_general_api_info_file(&dirs)?,
+            };
+            debug!("General info file: {}", found_general_info);
+
+            let type_overrides = if Path::new(overrides_file).exists() {
+                parser::TypeOverrides::from_file(overrides_file)?
+            } else {
+                parser::TypeOverrides::default()
+            };
+            let parser = parser::GoParser::new().with_type_overrides(type_overrides);
+            let api_info = parser.parse_general_api_info(&found_general_info)?;
+            let base_dir = find_base_dir(&found_general_info);
+            let (operations, schemas) =
+                parser.parse_operations(&dirs, &excluded_dirs, &base_dir)?;
+
+            let findings = linter::Linter::new(config).lint(&api_info, &operations, &schemas);
+            match format.as_str() {
+                "text" => print!("{}", linter::format_text(&findings)),
+                "json" => println!("{}", linter::format_json(&findings)?),
+                _ => anyhow::bail!("Unknown lint output format: {}", format),
+            }
+
+            let errors = findings
+                .iter()
+                .filter(|finding| finding.severity == linter::Severity::Error)
+                .count();
+            if errors > 0 {
+                anyhow::bail!("Lint found {} error(s)", errors);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Finds the directory containing go.mod, walking up from the general API info
+/// file, for resolving imports
+fn find_base_dir(general_info: &str) -> PathBuf {
+    let general_info_dir = Path::new(general_info).parent().unwrap_or(Path::new("."));
+
+    let mut current_dir = general_info_dir.to_path_buf();
+    let mut go_mod_dir = current_dir.clone();
+
+    // Try to locate go.mod file by walking up directories
+    while current_dir.parent().is_some() {
+        let go_mod = current_dir.join("go.mod");
+        if go_mod.exists() {
+            go_mod_dir = current_dir.clone();
+            break;
+        }
+        current_dir = current_dir.parent().unwrap().to_path_buf();
+    }
+
+    go_mod_dir
+}
+
 /// Finds a file containing general API info by searching common main.go files
 fn find_general_api_info_file(dirs: &[String]) -> Result<String> {
     // Common locations for main.go or similar files
@@ -23,6 +23,9 @@ pub struct OpenAPI {
     pub tags: Vec<Tag>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub externalDocs: Option<ExternalDocs>,
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// Server object used for API endpoints
@@ -165,6 +168,9 @@ pub struct Info {
     pub license: Option<License>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// Contact information for the API
@@ -204,6 +210,9 @@ pub struct Tag {
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub externalDocs: Option<ExternalDocs>,
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// A single path item with all its operations
@@ -235,6 +244,57 @@ pub struct PathItem {
     pub parameters: Vec<Parameter>,
 }
 
+impl PathItem {
+    /// The operations of the path item, mutably
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = &mut Operation> {
+        [
+            &mut self.get,
+            &mut self.post,
+            &mut self.put,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Set the operation for an HTTP method; returns false for unknown methods
+    pub fn set_operation(&mut self, method: &str, operation: Operation) -> bool {
+        let slot = match method {
+            "get" => &mut self.get,
+            "post" => &mut self.post,
+            "put" => &mut self.put,
+            "delete" => &mut self.delete,
+            "options" => &mut self.options,
+            "head" => &mut self.head,
+            "patch" => &mut self.patch,
+            "trace" => &mut self.trace,
+            _ => return false,
+        };
+        *slot = Some(operation);
+        true
+    }
+
+    /// The operations defined on this path item
+    pub fn operations(&self) -> impl Iterator<Item = &Operation> {
+        [
+            &self.get,
+            &self.post,
+            &self.put,
+            &self.delete,
+            &self.options,
+            &self.head,
+            &self.patch,
+            &self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
 /// An operation (endpoint) of the API
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Operation {
@@ -267,6 +327,46 @@ pub struct Operation {
     // Added for internal use, not serialized to OpenAPI
     #[serde(skip)]
     pub produces: Vec<String>,
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
+}
+
+impl Operation {
+    /// Call `visit` on every schema used by parameters, request body, responses and callbacks
+    pub fn walk_schemas_mut<F: FnMut(&mut Schema)>(&mut self, visit: &mut F) {
+        let media_types = self
+            .parameters
+            .iter_mut()
+            .flat_map(|p| p.content.values_mut())
+            .chain(self.requestBody.iter_mut().flat_map(|b| b.content.values_mut()))
+            .chain(self.responses.values_mut().flat_map(|r| r.content.values_mut()));
+        for media_type in media_types {
+            if let Some(schema) = &mut media_type.schema {
+                schema.walk_mut(visit);
+            }
+        }
+
+        let schemas = self
+            .parameters
+            .iter_mut()
+            .filter_map(|p| p.schema.as_mut())
+            .chain(
+                self.responses
+                    .values_mut()
+                    .flat_map(|r| r.headers.values_mut())
+                    .filter_map(|h| h.schema.as_mut()),
+            );
+        for schema in schemas {
+            schema.walk_mut(visit);
+        }
+
+        for path_item in self.callbacks.values_mut().flat_map(|c| c.values_mut()) {
+            for operation in path_item.operations_mut() {
+                operation.walk_schemas_mut(visit);
+            }
+        }
+    }
 }
 
 /// Parameter for an operation
@@ -384,7 +484,10 @@ pub struct Schema {
     // JSON Schema validation keywords
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub type_: Option<serde_json::Value>, // Can be a string or array of strings
+    // OpenAPI 3.0 only; 3.1 adds "null" to the type instead
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
     pub const_: Option<serde_json::Value>,
     #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
     pub enum_values: Option<Vec<serde_json::Value>>,
@@ -394,12 +497,14 @@ pub struct Schema {
     pub multipleOf: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
+    // The bound itself in OpenAPI 3.1, a boolean qualifying maximum in 3.0
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusiveMaximum: Option<f64>,
+    pub exclusiveMaximum: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<f64>,
+    // The bound itself in OpenAPI 3.1, a boolean qualifying minimum in 3.0
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusiveMinimum: Option<f64>,
+    pub exclusiveMinimum: Option<serde_json::Value>,
 
     // String validation
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -462,11 +567,11 @@ pub struct Schema {
     pub unevaluatedProperties: Option<serde_json::Value>, // Can be a boolean or Schema
 
     // Conditional schema
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
     pub if_: Option<Box<Schema>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub then: Option<Box<Schema>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "else", skip_serializing_if = "Option::is_none")]
     pub else_: Option<Box<Schema>>,
 
     // Content validation
@@ -476,6 +581,54 @@ pub struct Schema {
     pub contentMediaType: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contentSchema: Option<Box<Schema>>,
+
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
+}
+
+impl Schema {
+    /// Call `visit` on this schema and every schema nested inside it
+    pub fn walk_mut<F: FnMut(&mut Schema)>(&mut self, visit: &mut F) {
+        visit(self);
+
+        let boxed = self
+            .defs_
+            .values_mut()
+            .chain(self.properties.values_mut())
+            .chain(self.patternProperties.iter_mut().flat_map(|p| p.values_mut()))
+            .chain(self.propertyNames.iter_mut())
+            .chain(self.not.iter_mut())
+            .chain(self.items.iter_mut())
+            .chain(self.contains.iter_mut())
+            .chain(self.if_.iter_mut())
+            .chain(self.then.iter_mut())
+            .chain(self.else_.iter_mut())
+            .chain(self.contentSchema.iter_mut());
+        for schema in boxed {
+            schema.walk_mut(visit);
+        }
+
+        let lists = self
+            .allOf
+            .iter_mut()
+            .chain(self.anyOf.iter_mut())
+            .chain(self.oneOf.iter_mut())
+            .chain(self.prefixItems.iter_mut());
+        for schema in lists.flatten() {
+            schema.walk_mut(visit);
+        }
+
+        // additionalProperties is either a boolean or a schema
+        if let Some(value @ serde_json::Value::Object(_)) = &mut self.additionalProperties {
+            if let Ok(mut schema) = serde_json::from_value::<Schema>(value.clone()) {
+                schema.walk_mut(visit);
+                if let Ok(updated) = serde_json::to_value(schema) {
+                    *value = updated;
+                }
+            }
+        }
+    }
 }
 
 /// XML object
@@ -520,6 +673,9 @@ pub struct SecurityScheme {
     pub flows: Option<OAuthFlows>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub openIdConnectUrl: Option<String>,
+    // Specification extensions (x-...)
+    #[serde(flatten, skip_serializing_if = "HashMap::is_empty")]
+    pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// OAuth Flows Object
@@ -553,6 +709,8 @@ pub struct ParsedOperation {
     pub path: String,
     pub method: String,
     pub operation: Operation,
+    /// Declared with `@Webhook`; `path` is then the webhook name
+    pub webhook: bool,
 }
 
 /// Represents parsed general API info from Go code comments
@@ -565,6 +723,8 @@ pub struct ParsedApiInfo {
     pub security_definitions: HashMap<String, SecurityScheme>,
     pub tags: Vec<Tag>,
     pub external_docs: Option<ExternalDocs>,
+    // Document-level extensions (x-...)
+    pub extensions: HashMap<String, serde_json::Value>,
     // Legacy fields to maintain compatibility with Swagger 2.0 parsers
     pub host: Option<String>,
     pub base_path: Option<String>,
@@ -590,6 +750,7 @@ impl ParsedApiInfo {
                 contact: None,
                 license: None,
                 summary: None,
+                extensions: HashMap::new(),
             },
             servers: Vec::new(),
             host: None,
@@ -601,6 +762,7 @@ impl ParsedApiInfo {
             security: Vec::new(),
             tags: Vec::new(),
             external_docs: None,
+            extensions: HashMap::new(),
         }
     }
 }
@@ -4,9 +4,8 @@ use anyhow::{Context, Result};
 use log::{debug, warn};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
-use std::sync::Mutex;
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -14,19 +13,59 @@ use std::{
 };
 use thiserror::Error;
 
+use crate::go_ast::{self, CommentGroup, FuncDecl, GoFile, ImportSpec, TypeExpr, TypeSpec};
 use crate::models::{
-    Contact, ExternalDocs, License, MediaType, OAuthFlows, Operation, Parameter, ParsedApiInfo,
-    ParsedOperation, RequestBody, Response, Schema, SecurityScheme, Server,
+    Contact, Encoding, ExternalDocs, Header, License, MediaType, OAuthFlows, Operation, Parameter,
+    ParsedApiInfo, ParsedOperation, RequestBody, Response, Schema, SecurityScheme, Server,
 };
 
+// Matches the text of a comment line, without its `//` marker
 static ANNOTATION_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"//\s*@(\w+)(?:\.([\w.]+))?\s+(.+)$").unwrap());
+    Lazy::new(|| Regex::new(r"^\s*@([\w-]+)(?:\.([\w.-]+))?(?:\s+(.+))?$").unwrap());
 
 static MULTI_LINE_DESCRIPTION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"//\s*(.+)$").unwrap());
 
 static ROUTER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/(.+?)\s+\[(\w+)]$").unwrap());
 
+// `minimum(1)`, `Enums(a, b)` and the like after the description of a @Param
+static PARAM_ATTRIBUTE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\w+)\(([^)]*)\)").unwrap());
+
+// `{id}` parameters of an OpenAPI path template
+static PATH_PARAM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{([^{}/]+)\}").unwrap());
+
+// `newPet [post]`
+static WEBHOOK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\S+)\s+\[(\w+)]$").unwrap());
+
+// `200`, `4XX` or `default`
+static STATUS_CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[1-5]\d\d|[1-5]XX|default)$").unwrap());
+
+static HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\S+)\s+\{([^}]+)\}\s+(\S+)(?:\s+(.*))?$").unwrap());
+
+// `onPaymentEvent {$request.body#/callbackUrl} [post] models.PaymentEvent "description"`
+static CALLBACK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(\S+)\s+(\S+)\s+\[(\w+)](?:\s+([^\s"]+))?(?:\s+"?([^"]*)"?)?$"#).unwrap()
+});
+
+static PARAM_BODY_MODEL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@Param\s+\w+\s+body\s+([a-zA-Z0-9_.]+)").unwrap());
+
+static RESPONSE_BODY_MODEL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"@(?:Success|Failure)\s+\d+\s+\{object\}\s+([a-zA-Z0-9_.]+)").unwrap()
+});
+
+/// Response schemas that are always part of the generated components
+pub const COMMON_SCHEMA_NAMES: [&str; 4] = [
+    "response.ApiResponse",
+    "response.Response",
+    "response.OpenApiResponse",
+    "response.OpenApiErrorNonSnap",
+];
+
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum ParserError {
     #[error("Failed to read file: {0}")]
     IOError(#[from] std::io::Error),
@@ -44,15 +83,111 @@ pub enum ParserError {
     #[error("Invalid response format: {0}")]
     ResponseParseError(String),
     
+    #[error("Invalid header format: {0}")]
+    HeaderParseError(String),
+
+    #[error("Invalid webhook format: {0}")]
+    WebhookParseError(String),
+
+    #[error("Invalid callback format: {0}")]
+    CallbackParseError(String),
+
     #[error("Invalid security format: {0}")]
     SecurityParseError(String),
     
     #[error("Invalid general API info: {0}")]
-    #[allow(dead_code)]
     GeneralApiInfoError(String),
     
     #[error("Invalid server format: {0}")]
     ServerParseError(String),
+
+    #[error("Invalid overrides file: {0}")]
+    OverridesParseError(String),
+
+    #[error("Unknown annotation: @{0}")]
+    UnknownAnnotation(String),
+
+    #[error("Unresolved schema reference: no definition found for {0}")]
+    UnresolvedReference(String),
+
+    #[error("Unknown attribute: {0}")]
+    UnknownAttribute(String),
+
+    /// An error about an annotation, reported at the place it was written
+    #[error("{}", .location.render(&.error.to_string()))]
+    Located {
+        location: SourceLocation,
+        error: Box<ParserError>,
+    },
+}
+
+impl ParserError {
+    /// Report this error at an annotation's location, when it is known
+    pub fn at(self, location: Option<&SourceLocation>) -> Self {
+        match location {
+            Some(location) => Self::Located {
+                location: location.clone(),
+                error: Box::new(self),
+            },
+            None => self,
+        }
+    }
+}
+
+/// Where an annotation was written
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceLocation {
+    pub file: PathBuf,
+    /// 1-based line and column of the annotation's `@`
+    pub line: usize,
+    pub column: usize,
+    /// The source line holding the annotation
+    pub source_line: String,
+}
+
+impl SourceLocation {
+    /// Format a message the way compilers do: `path:line:col: message`, followed
+    /// by the source line with a caret under the annotation
+    pub fn render(&self, message: &str) -> String {
+        let source_line = self.source_line.trim_end();
+        let gutter = " ".repeat(self.line.to_string().len());
+        // Tabs before the annotation are kept so the caret lines up under it
+        let indent: String = source_line
+            .chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = source_line
+            .chars()
+            .count()
+            .saturating_sub(self.column.saturating_sub(1))
+            .max(1);
+        format!(
+            "{}: {}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            message,
+            gutter,
+            self.line,
+            source_line,
+            gutter,
+            indent,
+            "^".repeat(width)
+        )
+    }
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)
+    }
+}
+
+// A warning message, rendered at the annotation it is about when its location is known
+fn diagnostic(location: Option<&SourceLocation>, message: &str) -> String {
+    match location {
+        Some(location) => location.render(message),
+        None => message.to_string(),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -82,6 +217,9 @@ pub enum AnnotationType {
     // Security
     SecurityDefinitions,
     SecurityScheme,
+    // `@in`, `@name`, `@tokenUrl`, `@authorizationUrl` and `@scope.*` lines under a
+    // security definition, keyed by the annotation name as written
+    SecurityAttribute(String),
     
     // External Docs
     ExternalDocs,
@@ -91,12 +229,17 @@ pub enum AnnotationType {
     Tags,
     Router,
     DeprecatedRouter,
+    Webhook,
     Param,
     RequestBody,
     Security,
     Response,
     Header,
     Deprecated,
+    Callback,
+    
+    // Vendor extensions such as @x-logo, keyed by the full `x-` name
+    Extension(String),
     
     // Unknown
     Unknown(String),
@@ -121,11 +264,15 @@ impl From<&str> for AnnotationType {
             "tag" => Self::Tag,
             "securitydefinitions" => Self::SecurityDefinitions,
             "securityscheme" => Self::SecurityScheme,
+            "in" | "name" | "tokenurl" | "authorizationurl" | "scope" => {
+                Self::SecurityAttribute(s.to_string())
+            }
             "externaldocs" => Self::ExternalDocs,
             "id" => Self::Id,
             "tags" => Self::Tags,
             "router" => Self::Router,
             "deprecatedrouter" => Self::DeprecatedRouter,
+            "webhook" => Self::Webhook,
             "param" => Self::Param,
             "requestbody" => Self::RequestBody,
             "security" => Self::Security,
@@ -134,6 +281,8 @@ impl From<&str> for AnnotationType {
             "failure" => Self::Response, // For backward compatibility
             "header" => Self::Header,
             "deprecated" => Self::Deprecated,
+            "callback" => Self::Callback,
+            lower if lower.starts_with("x-") => Self::Extension(s.to_string()),
             _ => Self::Unknown(s.to_string()),
         }
     }
@@ -144,9 +293,566 @@ pub struct Annotation {
     pub annotation_type: AnnotationType,
     pub attribute: Option<String>,
     pub value: String,
+    pub location: Option<SourceLocation>,
+}
+
+/// One `@Router` or `@Webhook` line of a handler
+#[derive(Debug, Clone)]
+struct Route {
+    path: String,
+    method: String,
+    /// Patterns of gorilla-style `{id:[0-9]+}` parameters, keyed by parameter name
+    patterns: HashMap<String, String>,
+    /// Declared with `@DeprecatedRouter`
+    deprecated: bool,
+    webhook: bool,
+    /// The `@Router` or `@Webhook` annotation declaring the route
+    location: Option<SourceLocation>,
+}
+
+/// A callback declared with `@Callback`, and the annotations describing its operation
+#[derive(Debug, Clone)]
+struct CallbackDecl {
+    name: String,
+    expression: String,
+    method: String,
+    annotations: Vec<Annotation>,
+    location: Option<SourceLocation>,
+}
+
+/// A Go struct tag such as `json:"user_id,omitempty" example:"42"`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructTag {
+    entries: Vec<(String, String)>,
+}
+
+impl StructTag {
+    /// Parse the raw tag text (without the surrounding backticks) following
+    /// the `key:"value"` convention used by `reflect.StructTag`
+    pub fn parse(raw: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut rest = raw.trim();
+
+        while !rest.is_empty() {
+            let Some(colon) = rest.find(':') else {
+                break;
+            };
+            let key = rest[..colon].trim();
+            let after_key = &rest[colon + 1..];
+            if key.is_empty() || !after_key.starts_with('"') {
+                break;
+            }
+
+            // Find the closing quote, honouring escaped quotes inside the value
+            let bytes = after_key.as_bytes();
+            let mut end = 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                if bytes[end] == b'\\' {
+                    end += 1;
+                }
+                end += 1;
+            }
+            if end >= bytes.len() {
+                break;
+            }
+
+            let value = after_key[1..end].replace("\\\"", "\"");
+            entries.push((key.to_string(), value));
+            rest = after_key[end + 1..].trim_start();
+        }
+
+        Self { entries }
+    }
+
+    /// Look up the value stored under `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A single field declaration inside a Go struct body
+#[derive(Debug, Clone)]
+struct StructField {
+    name: String,
+    ty: TypeExpr,
+    tag: StructTag,
+    embedded: bool,
+    /// The comment above the field, or else the one trailing it
+    doc: Option<CommentGroup>,
+}
+
+impl StructField {
+    /// Fields declared by an AST field; `X, Y int` declares two
+    fn from_ast(field: &go_ast::Field) -> Vec<StructField> {
+        let tag = field.tag.as_deref().map(StructTag::parse).unwrap_or_default();
+        let doc = field.doc.clone().or_else(|| field.comment.clone());
+
+        if field.is_embedded() {
+            // Embedded fields are named after their type, without package or type arguments
+            let mut base = &field.ty;
+            while let TypeExpr::Pointer(inner) = base {
+                base = inner;
+            }
+            let TypeExpr::Named { name, .. } = base else {
+                return Vec::new();
+            };
+            return vec![StructField {
+                name: name.clone(),
+                ty: field.ty.clone(),
+                tag,
+                embedded: true,
+                doc,
+            }];
+        }
+
+        field
+            .names
+            .iter()
+            .map(|name| StructField {
+                name: name.clone(),
+                ty: field.ty.clone(),
+                tag: tag.clone(),
+                embedded: false,
+                doc: doc.clone(),
+            })
+            .collect()
+    }
+
+    /// The field type as written in Go, e.g. `[]*models.User`
+    fn type_name(&self) -> String {
+        self.ty.to_string()
+    }
+
+    /// Name of the property as produced by encoding/json, or `None` when
+    /// the field is excluded with `json:"-"`
+    fn json_name(&self) -> Option<String> {
+        match self.tag.get("json") {
+            Some("-") => None,
+            Some(json) => {
+                let name = json.split(',').next().unwrap_or("");
+                if name.is_empty() {
+                    Some(self.name.clone())
+                } else {
+                    Some(name.to_string())
+                }
+            }
+            None => Some(self.name.clone()),
+        }
+    }
+
+    /// Whether the json tag carries the `omitempty` option
+    fn is_omitempty(&self) -> bool {
+        self.tag
+            .get("json")
+            .is_some_and(|json| json.split(',').skip(1).any(|opt| opt.trim() == "omitempty"))
+    }
+
+    /// Validation rules from gin `binding` and go-playground `validate` tags
+    fn validation_rules(&self) -> Vec<String> {
+        ["binding", "validate"]
+            .iter()
+            .filter_map(|key| self.tag.get(key))
+            .flat_map(|rules| rules.split(','))
+            .map(|rule| rule.trim().to_string())
+            .filter(|rule| !rule.is_empty())
+            .collect()
+    }
+
+    /// Vendor extensions from an `extensions:"x-nullable,x-abc=def,!x-omitempty"` tag
+    fn extensions(&self) -> Vec<(String, serde_json::Value)> {
+        self.tag
+            .get("extensions")
+            .map(parse_extension_list)
+            .unwrap_or_default()
+    }
+
+    /// Embedded fields without a json name have their fields promoted into
+    /// the enclosing struct, as encoding/json does
+    fn is_promoted(&self) -> bool {
+        self.embedded
+            && self
+                .tag
+                .get("json")
+                .is_none_or(|json| json.split(',').next().unwrap_or("").is_empty())
+    }
+
+    /// Whether a `required` validation rule applies to the field itself
+    fn has_required_rule(&self) -> bool {
+        self.validation_rules()
+            .iter()
+            .take_while(|rule| rule.as_str() != "dive")
+            .any(|rule| rule == "required")
+    }
+
+    /// The parameter name of the field when its struct is expanded into parameters:
+    /// the `form`/`query` tag for query strings, `header` for headers and `uri`/`path`
+    /// for path parameters, falling back to the json name
+    fn param_name(&self, in_type: &str) -> Option<String> {
+        let keys: &[&str] = match in_type {
+            "query" => &["form", "query"],
+            "header" => &["header"],
+            "path" => &["uri", "path"],
+            _ => &[],
+        };
+        for key in keys {
+            if let Some(tag) = self.tag.get(key) {
+                match tag.split(',').next().unwrap_or("").trim() {
+                    "-" => return None,
+                    "" => {}
+                    name => return Some(name.to_string()),
+                }
+            }
+        }
+        self.json_name()
+    }
+}
+
+/// How a Go type listed in the overrides file is documented
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeOverride {
+    /// Document the type as another Go type or OpenAPI primitive, optionally with a format
+    Replace {
+        target: String,
+        format: Option<String>,
+    },
+    /// Leave fields of this type out of the schemas
+    Skip,
+}
+
+/// Project type overrides, read from a swag-style `.swaggo` file:
+///
+/// ```text
+/// // Document NullInt64 as a plain integer
+/// replace database/sql.NullInt64 int
+/// replace github.com/shopspring/decimal.Decimal string decimal
+/// skip database/sql.NullString
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TypeOverrides {
+    entries: HashMap<String, TypeOverride>,
+}
+
+impl TypeOverrides {
+    /// Read overrides from a file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read overrides file: {:?}", path))?;
+        Ok(Self::parse(&content)?)
+    }
+
+    /// Parse the `replace <type> <target> [format]` and `skip <type>` directives
+    pub fn parse(content: &str) -> Result<Self, ParserError> {
+        let mut entries = HashMap::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let (key, type_override) = match parts.as_slice() {
+                ["replace", type_name, target] => (
+                    type_name,
+                    TypeOverride::Replace {
+                        target: target.to_string(),
+                        format: None,
+                    },
+                ),
+                ["replace", type_name, target, format] => (
+                    type_name,
+                    TypeOverride::Replace {
+                        target: target.to_string(),
+                        format: Some(format.to_string()),
+                    },
+                ),
+                ["skip", type_name] => (type_name, TypeOverride::Skip),
+                _ => {
+                    return Err(ParserError::OverridesParseError(format!(
+                        "line {}: expected `replace <type> <target> [format]` or `skip <type>`, got: {}",
+                        index + 1,
+                        line
+                    )))
+                }
+            };
+
+            entries.insert(Self::package_qualified(key), type_override);
+        }
+
+        // A replacement target that is itself replaced could recurse forever
+        for (type_name, type_override) in &entries {
+            if let TypeOverride::Replace { target, .. } = type_override {
+                if entries.contains_key(&Self::package_qualified(target)) {
+                    return Err(ParserError::OverridesParseError(format!(
+                        "replacement target {} of {} is itself overridden",
+                        target, type_name
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Look up the override for a type as written in Go source, e.g. `sql.NullString`
+    pub fn get(&self, type_name: &str) -> Option<&TypeOverride> {
+        self.entries.get(type_name)
+    }
+
+    // Types are referenced by package name in Go code, so `database/sql.NullString`
+    // is keyed as `sql.NullString`
+    fn package_qualified(type_name: &str) -> String {
+        type_name
+            .rsplit_once('/')
+            .map_or(type_name, |(_, name)| name)
+            .to_string()
+    }
+}
+
+/// A constant declared with a named type, collected as a member of that type's enum
+#[derive(Debug, Clone)]
+struct EnumValue {
+    name: String,
+    value: serde_json::Value,
+    comment: Option<String>,
+}
+
+/// A Go source file parsed into its declarations
+struct SourceFile {
+    path: PathBuf,
+    source: String,
+    ast: GoFile,
+}
+
+impl SourceFile {
+    /// The location of a position in this file, for diagnostics
+    fn location(&self, pos: &go_ast::Position) -> SourceLocation {
+        SourceLocation {
+            file: self.path.clone(),
+            line: pos.line,
+            column: pos.column,
+            source_line: self.source.lines().nth(pos.line - 1).unwrap_or("").to_string(),
+        }
+    }
+}
+
+/// Type declarations by name, with the file declaring each
+type TypeIndex<'a> = HashMap<&'a str, Vec<(&'a SourceFile, &'a TypeSpec)>>;
+
+fn index_type_specs(files: &[SourceFile]) -> TypeIndex<'_> {
+    let mut type_specs: TypeIndex = HashMap::new();
+    for file in files {
+        for spec in &file.ast.types {
+            type_specs.entry(spec.name.as_str()).or_default().push((file, spec));
+        }
+    }
+    type_specs
+}
+
+// The declaration of `name` and its file, preferring the package a qualified name
+// such as `models.User` points at
+fn lookup_type_decl<'a>(
+    type_specs: &TypeIndex<'a>,
+    package: Option<&str>,
+    name: &str,
+) -> Option<(&'a SourceFile, &'a TypeSpec)> {
+    type_specs.get(name).and_then(|candidates| {
+        candidates
+            .iter()
+            .find(|(file, _)| Some(file.ast.package.as_str()) == package)
+            .or(candidates.first())
+            .copied()
+    })
+}
+
+// The declaration of `name`, as `lookup_type_decl` finds it
+fn lookup_type_spec<'a>(
+    type_specs: &TypeIndex<'a>,
+    package: Option<&str>,
+    name: &str,
+) -> Option<&'a TypeSpec> {
+    lookup_type_decl(type_specs, package, name).map(|(_, spec)| spec)
+}
+
+// Let a schema also accept null: `type: T` becomes `type: [T, "null"]`, and a
+// reference becomes `anyOf: [$ref, {type: "null"}]`
+fn make_nullable(schema: &mut Schema) {
+    let null = serde_json::Value::String("null".to_string());
+    if schema.ref_.is_some() {
+        let reference = std::mem::take(schema);
+        schema.anyOf = Some(vec![
+            reference,
+            Schema {
+                type_: Some(null),
+                ..Default::default()
+            },
+        ]);
+        return;
+    }
+
+    match schema.type_.take() {
+        Some(serde_json::Value::Array(mut types)) => {
+            if !types.contains(&null) {
+                types.push(null.clone());
+            }
+            schema.type_ = Some(serde_json::Value::Array(types));
+        }
+        Some(type_) => schema.type_ = Some(serde_json::Value::Array(vec![type_, null.clone()])),
+        // Schemas without a type already accept null
+        None => return,
+    }
+    if let Some(values) = &mut schema.enum_values {
+        if !values.contains(&serde_json::Value::Null) {
+            values.push(serde_json::Value::Null);
+        }
+    }
+}
+
+// Turn swag's `file` data type (also as array items) into `type: string, format: binary`;
+// returns whether the schema describes files
+fn file_to_binary(schema: &mut Schema) -> bool {
+    if let Some(items) = &mut schema.items {
+        return file_to_binary(items);
+    }
+    if schema.type_.as_ref().and_then(|t| t.as_str()) != Some("file") {
+        return false;
+    }
+    schema.type_ = Some(serde_json::Value::String("string".to_string()));
+    schema.format = Some("binary".to_string());
+    true
+}
+
+// Convert an attribute value to the parameter's type, e.g. `Default(10)` on an
+// integer parameter is the number 10; array values are comma-separated items
+fn coerce_param_value(value: &str, schema: &Schema) -> serde_json::Value {
+    let value = value.trim();
+    let type_ = schema.type_.as_ref().and_then(|t| t.as_str()).unwrap_or("");
+    let coerced = match type_ {
+        "integer" => value.parse::<i64>().ok().map(serde_json::Value::from),
+        "number" => value.parse::<f64>().ok().map(serde_json::Value::from),
+        "boolean" => value.parse::<bool>().ok().map(serde_json::Value::from),
+        "array" => {
+            let items = schema.items.as_deref().cloned().unwrap_or_default();
+            let values = value
+                .split(',')
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| coerce_param_value(v, &items))
+                .collect();
+            Some(serde_json::Value::Array(values))
+        }
+        "string" => Some(serde_json::Value::String(value.to_string())),
+        // Models and untyped values take JSON literals
+        _ => serde_json::from_str(value).ok(),
+    };
+    coerced.unwrap_or_else(|| serde_json::Value::String(value.to_string()))
+}
+
+// Extension values are JSON when they parse as such (`true`, `42`, `{"url": ...}`),
+// and plain strings otherwise
+fn parse_extension_value(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
+// Rewrite a router path into an OpenAPI path template: gin/echo `:id` and
+// `*filepath` segments become `{id}` and `{filepath}`, and gorilla `{id:[0-9]+}`
+// becomes `{id}`, with the regular expression returned as the parameter's pattern
+fn normalize_route_path(path: &str) -> (String, HashMap<String, String>) {
+    let mut patterns = HashMap::new();
+    let mut normalized = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        normalized.push_str(&rest[..start]);
+        // Find the matching brace; gorilla patterns may contain `{n}` quantifiers
+        let mut depth = 0;
+        let mut end = None;
+        for (i, c) in rest[start..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(start + i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            normalized.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let inner = &rest[start + 1..end];
+        match inner.split_once(':') {
+            Some((name, pattern)) => {
+                patterns.insert(name.to_string(), format!("^{}$", pattern));
+                normalized.push_str(&format!("{{{}}}", name));
+            }
+            None => normalized.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    normalized.push_str(rest);
+
+    let normalized = normalized
+        .split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+            Some(name) if !name.is_empty() => format!("{{{}}}", name),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (normalized, patterns)
+}
+
+// Parse `x-a=b,x-flag,!x-other` extension lists: bare names are `true` and names
+// prefixed with `!` are `false`; entries without an `x-` prefix are ignored
+fn parse_extension_list(list: &str) -> Vec<(String, serde_json::Value)> {
+    list.split(',')
+        .map(str::trim)
+        .filter_map(|entry| {
+            let (name, value) = match entry.split_once('=') {
+                Some((name, value)) => (name, parse_extension_value(value)),
+                None => match entry.strip_prefix('!') {
+                    Some(name) => (name, serde_json::Value::Bool(false)),
+                    None => (entry, serde_json::Value::Bool(true)),
+                },
+            };
+            name.starts_with("x-").then(|| (name.to_string(), value))
+        })
+        .collect()
+}
+
+// Whether a doc comment has a `Deprecated:` paragraph, the Go convention for deprecation
+fn is_deprecation_notice(text: &str) -> bool {
+    text.split("\n\n")
+        .any(|paragraph| paragraph.trim_start().starts_with("Deprecated:"))
+}
+
+// The first sentence of the first paragraph of a doc comment, like go/doc's synopsis
+fn first_sentence(text: &str) -> String {
+    let paragraph = text.split("\n\n").next().unwrap_or(text);
+    let paragraph = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+    match paragraph.find(". ") {
+        Some(end) => paragraph[..=end].to_string(),
+        None => paragraph,
+    }
 }
 
-pub struct GoParser;
+pub struct GoParser {
+    embedded_all_of: bool,
+    type_overrides: TypeOverrides,
+    // Annotations dropped while parsing, for strict mode
+    problems: RefCell<Vec<ParserError>>,
+}
 
 impl Default for GoParser {
     fn default() -> Self {
@@ -163,7 +869,42 @@ pub struct ImportInfo {
 
 impl GoParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            embedded_all_of: false,
+            type_overrides: TypeOverrides::default(),
+            problems: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Take the problems found so far: annotations that were dropped because
+    /// they are invalid or unknown, and referenced models without a definition
+    pub fn take_problems(&self) -> Vec<ParserError> {
+        // Annotations in the general info file are read by both passes
+        let mut seen = HashSet::new();
+        self.problems
+            .take()
+            .into_iter()
+            .filter(|problem| seen.insert(problem.to_string()))
+            .collect()
+    }
+
+    // Warn about a dropped annotation and remember it for strict mode
+    fn report(&self, problem: ParserError) {
+        warn!("{}", problem);
+        self.problems.borrow_mut().push(problem);
+    }
+
+    /// Apply project type overrides on top of the built-in well-known type mappings
+    pub fn with_type_overrides(mut self, type_overrides: TypeOverrides) -> Self {
+        self.type_overrides = type_overrides;
+        self
+    }
+
+    /// Emit structs with embedded fields as an `allOf` of the embedded
+    /// schemas and their own properties instead of flattening them
+    pub fn with_embedded_all_of(mut self, embedded_all_of: bool) -> Self {
+        self.embedded_all_of = embedded_all_of;
+        self
     }
     
     pub fn parse_general_api_info(&self, file_path: impl AsRef<Path>) -> Result<ParsedApiInfo> {
@@ -181,22 +922,38 @@ impl GoParser {
         let mut description_buffer = String::new();
         let mut current_server: Option<Server> = None;
         let mut current_tag_name: Option<String> = None;
+        // Extensions that follow a security definition belong to that scheme
+        let mut current_security_scheme: Option<String> = None;
         
-        for line in reader.lines() {
+        for (index, line) in reader.lines().enumerate() {
             let line = line?;
             
             // Check for comment annotations
-            if let Some(captures) = ANNOTATION_REGEX.captures(&line) {
-                let annotation_type_str = captures.get(1).unwrap().as_str();
-                let attribute = captures.get(2).map(|m| m.as_str().to_string());
-                let value = captures.get(3).unwrap().as_str().to_string();
-                
-                let annotation = Annotation {
-                    annotation_type: AnnotationType::from(annotation_type_str),
-                    attribute,
-                    value,
-                };
-                
+            let line_annotation = line
+                .trim_start()
+                .strip_prefix("//")
+                .and_then(|comment| self.parse_annotation(comment))
+                .map(|mut annotation| {
+                    let at = line.find('@').unwrap_or(0);
+                    annotation.location = Some(SourceLocation {
+                        file: file_path.to_path_buf(),
+                        line: index + 1,
+                        column: line[..at].chars().count() + 1,
+                        source_line: line.clone(),
+                    });
+                    annotation
+                });
+            if let Some(annotation) = line_annotation {
+                match &annotation.annotation_type {
+                    AnnotationType::SecurityDefinitions
+                    | AnnotationType::SecurityScheme
+                    | AnnotationType::SecurityAttribute(_)
+                    | AnnotationType::Description
+                    | AnnotationType::Extension(_)
+                    | AnnotationType::Unknown(_) => {}
+                    _ => current_security_scheme = None,
+                }
+
                 match annotation.annotation_type {
                     AnnotationType::Title => {
                         api_info.info.title = annotation.value;
@@ -205,7 +962,13 @@ impl GoParser {
                         api_info.info.version = annotation.value;
                     }
                     AnnotationType::Description => {
-                        if !in_doc_comment {
+                        let scheme = current_security_scheme
+                            .as_ref()
+                            .and_then(|scheme| api_info.security_definitions.get_mut(scheme));
+                        if let Some(scheme) = scheme {
+                            // A description under a security definition describes the scheme
+                            scheme.description = Some(annotation.value);
+                        } else if !in_doc_comment {
                             api_info.info.description = Some(annotation.value);
                         } else {
                             description_buffer.push_str(&annotation.value);
@@ -224,7 +987,10 @@ impl GoParser {
                                 "name" => contact.name = Some(annotation.value),
                                 "url" => contact.url = Some(annotation.value),
                                 "email" => contact.email = Some(annotation.value),
-                                _ => warn!("Unknown contact attribute: {}", attribute),
+                                _ => self.report(
+                                    ParserError::UnknownAttribute(format!("@contact.{}", attribute))
+                                        .at(annotation.location.as_ref()),
+                                ),
                             }
                         }
                     }
@@ -234,7 +1000,10 @@ impl GoParser {
                                 "name" => license.name = annotation.value,
                                 "url" => license.url = Some(annotation.value),
                                 "identifier" => license.identifier = Some(annotation.value),
-                                _ => warn!("Unknown license attribute: {}", attribute),
+                                _ => self.report(
+                                    ParserError::UnknownAttribute(format!("@license.{}", attribute))
+                                        .at(annotation.location.as_ref()),
+                                ),
                             }
                         }
                     }
@@ -267,7 +1036,10 @@ impl GoParser {
                                         });
                                     }
                                 }
-                                _ => warn!("Unknown server attribute: {}", attribute),
+                                _ => self.report(
+                                    ParserError::UnknownAttribute(format!("@server.{}", attribute))
+                                        .at(annotation.location.as_ref()),
+                                ),
                             }
                         } else {
                             return Err(ParserError::ServerParseError(
@@ -322,6 +1094,7 @@ impl GoParser {
                                             name: annotation.value,
                                             description: None,
                                             externalDocs: None,
+                                            extensions: HashMap::new(),
                                         });
                                     }
                                 }
@@ -334,10 +1107,38 @@ impl GoParser {
                                             }
                                         }
                                     } else {
-                                        warn!("Tag description provided without a name");
+                                        self.report(
+                                            ParserError::GeneralApiInfoError(
+                                                "@tag.description without a preceding @tag.name"
+                                                    .to_string(),
+                                            )
+                                            .at(annotation.location.as_ref()),
+                                        );
+                                    }
+                                }
+                                extension if extension.starts_with("x-") => {
+                                    let tag = current_tag_name
+                                        .as_ref()
+                                        .and_then(|name| api_info.tags.iter_mut().find(|tag| &tag.name == name));
+                                    if let Some(tag) = tag {
+                                        tag.extensions.insert(
+                                            extension.to_string(),
+                                            parse_extension_value(&annotation.value),
+                                        );
+                                    } else {
+                                        self.report(
+                                            ParserError::GeneralApiInfoError(format!(
+                                                "@tag.{} without a preceding @tag.name",
+                                                extension
+                                            ))
+                                            .at(annotation.location.as_ref()),
+                                        );
                                     }
                                 }
-                                _ => warn!("Unknown tag attribute: {}", attribute),
+                                _ => self.report(
+                                    ParserError::UnknownAttribute(format!("@tag.{}", attribute))
+                                        .at(annotation.location.as_ref()),
+                                ),
                             }
                         } else {
                             // For backward compatibility, treat as a tag name
@@ -345,6 +1146,7 @@ impl GoParser {
                                 name: annotation.value,
                                 description: None,
                                 externalDocs: None,
+                                extensions: HashMap::new(),
                             });
                         }
                     }
@@ -354,11 +1156,16 @@ impl GoParser {
                                 &mut api_info,
                                 &attribute,
                                 &annotation.value,
-                            )?;
+                            )
+                            .map_err(|e| e.at(annotation.location.as_ref()))?;
+                            if api_info.security_definitions.contains_key(&annotation.value) {
+                                current_security_scheme = Some(annotation.value.clone());
+                            }
                         } else {
                             return Err(ParserError::SecurityParseError(
                                 "SecurityDefinitions annotation requires an attribute".to_string(),
                             )
+                            .at(annotation.location.as_ref())
                             .into());
                         }
                     }
@@ -383,13 +1190,29 @@ impl GoParser {
                                         "name" => {
                                             scheme.name = Some(annotation.value.clone());
                                         }
-                                        // Add other properties as needed
-                                        _ => {
-                                            warn!("Unknown security scheme property: {}", property)
+                                        extension if extension.starts_with("x-") => {
+                                            scheme.extensions.insert(
+                                                extension.to_string(),
+                                                parse_extension_value(&annotation.value),
+                                            );
                                         }
+                                        // Add other properties as needed
+                                        _ => warn!(
+                                            "{}",
+                                            diagnostic(
+                                                annotation.location.as_ref(),
+                                                &format!("Unknown security scheme property: {}", property)
+                                            )
+                                        ),
                                     }
                                 } else {
-                                    warn!("Security scheme not found: {}", scheme_name);
+                                    warn!(
+                                        "{}",
+                                        diagnostic(
+                                            annotation.location.as_ref(),
+                                            &format!("Security scheme not found: {}", scheme_name)
+                                        )
+                                    );
                                 }
                             }
                         }
@@ -434,10 +1257,52 @@ impl GoParser {
                                         });
                                     }
                                 }
-                                _ => warn!("Unknown external docs attribute: {}", attribute),
+                                _ => self.report(
+                                    ParserError::UnknownAttribute(format!("@externalDocs.{}", attribute))
+                                        .at(annotation.location.as_ref()),
+                                ),
                             }
                         }
                     }
+                    AnnotationType::SecurityAttribute(name) => {
+                        let scheme = current_security_scheme
+                            .as_ref()
+                            .and_then(|scheme| api_info.security_definitions.get_mut(scheme));
+                        let result = match scheme {
+                            Some(scheme) => self.apply_security_attribute(
+                                scheme,
+                                &name,
+                                annotation.attribute.as_deref(),
+                                &annotation.value,
+                            ),
+                            None => Err(ParserError::SecurityParseError(format!(
+                                "@{} must follow a @securityDefinitions line",
+                                name
+                            ))),
+                        };
+                        if let Err(e) = result {
+                            self.report(e.at(annotation.location.as_ref()));
+                        }
+                    }
+                    AnnotationType::Extension(name) => {
+                        let value = parse_extension_value(&annotation.value);
+                        let scheme = current_security_scheme
+                            .as_ref()
+                            .and_then(|scheme| api_info.security_definitions.get_mut(scheme));
+                        if let Some(scheme) = scheme {
+                            scheme.extensions.insert(name, value);
+                        } else if name == "x-logo" {
+                            // ReDoc reads the logo from the info object, as swag emits it
+                            api_info.info.extensions.insert(name, value);
+                        } else {
+                            api_info.extensions.insert(name, value);
+                        }
+                    }
+                    AnnotationType::Unknown(name) => {
+                        self.report(
+                            ParserError::UnknownAnnotation(name).at(annotation.location.as_ref()),
+                        );
+                    }
                     _ => {}
                 }
             } else if let Some(captures) = MULTI_LINE_DESCRIPTION_REGEX.captures(&line) {
@@ -489,12 +1354,12 @@ impl GoParser {
         }
         
         // Create a default server from legacy host/basePath/schemes if no servers were defined
-        if api_info.servers.is_empty() && api_info.host.is_some() {
+        if let (true, Some(host)) = (api_info.servers.is_empty(), api_info.host.clone()) {
             for scheme in &api_info.schemes {
                 let url = format!(
                     "{}://{}{}",
                     scheme,
-                    api_info.host.as_ref().unwrap(),
+                    host,
                     api_info.base_path.as_deref().unwrap_or("")
                 );
                 
@@ -509,6 +1374,72 @@ impl GoParser {
         Ok(api_info)
     }
     
+    // Apply a line of swag's security definition syntax, such as `@in header` or
+    // `@scope.read Grants read access`, to the scheme defined above it
+    fn apply_security_attribute(
+        &self,
+        scheme: &mut SecurityScheme,
+        name: &str,
+        attribute: Option<&str>,
+        value: &str,
+    ) -> Result<(), ParserError> {
+        let name_lower = name.to_lowercase();
+        match name_lower.as_str() {
+            // Only API keys are sent in a named header, query parameter or cookie
+            "in" | "name" if scheme.type_ != "apiKey" => {
+                debug!("Ignoring @{} on a {} security scheme", name, scheme.type_);
+            }
+            "in" => match value {
+                "header" | "query" | "cookie" => scheme.in_type = Some(value.to_string()),
+                _ => {
+                    return Err(ParserError::SecurityParseError(format!(
+                        "@in must be header, query or cookie, got {}",
+                        value
+                    )))
+                }
+            },
+            "name" => scheme.name = Some(value.to_string()),
+            _ => {
+                let flows = scheme.flows.as_mut().ok_or_else(|| {
+                    ParserError::SecurityParseError(format!(
+                        "@{} only applies to OAuth2 security definitions",
+                        name
+                    ))
+                })?;
+                let flow = match name_lower.as_str() {
+                    "authorizationurl" => flows.implicit.as_mut().or(flows.authorizationCode.as_mut()),
+                    "tokenurl" => flows
+                        .password
+                        .as_mut()
+                        .or(flows.clientCredentials.as_mut())
+                        .or(flows.authorizationCode.as_mut()),
+                    _ => flows
+                        .implicit
+                        .as_mut()
+                        .or(flows.password.as_mut())
+                        .or(flows.clientCredentials.as_mut())
+                        .or(flows.authorizationCode.as_mut()),
+                };
+                let flow = flow.ok_or_else(|| {
+                    ParserError::SecurityParseError(format!("@{} does not apply to this OAuth2 flow", name))
+                })?;
+                match name_lower.as_str() {
+                    "authorizationurl" => flow.authorizationUrl = Some(value.to_string()),
+                    "tokenurl" => flow.tokenUrl = Some(value.to_string()),
+                    _ => {
+                        let scope = attribute.ok_or_else(|| {
+                            ParserError::SecurityParseError(
+                                "@scope needs a scope name, as in @scope.read".to_string(),
+                            )
+                        })?;
+                        flow.scopes.insert(scope.to_string(), value.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn parse_security_definition(
         &self,
         api_info: &mut ParsedApiInfo,
@@ -582,12 +1513,10 @@ impl GoParser {
                 if let Some(scheme) = api_info.security_definitions.get_mut("OAuth2") {
                     if let Some(ref mut flows) = scheme.flows {
                         match property.as_str() {
-                            "authorizationUrl" => {
-                                if flow_type == "implicit" {
-                                    if let Some(ref mut implicit) = flows.implicit {
-                                        implicit.authorizationUrl = Some(value.to_string());
-                                        return Ok(());
-                                    }
+                            "authorizationUrl" if flow_type == "implicit" => {
+                                if let Some(ref mut implicit) = flows.implicit {
+                                    implicit.authorizationUrl = Some(value.to_string());
+                                    return Ok(());
                                 }
                             }
                             "tokenUrl" => {
@@ -643,42 +1572,40 @@ impl GoParser {
                                     }
                                 }
                             }
-                            "scopes" => {
-                                if parts.len() >= 4 {
-                                    let scope_name = parts[3].to_string();
-                                    
-                                    if flow_type == "implicit" {
-                                        if let Some(ref mut implicit) = flows.implicit {
-                                            implicit.scopes.insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "password" {
-                                        if let Some(ref mut password) = flows.password {
-                                            password.scopes.insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "clientcredentials"
-                                        || flow_type == "application"
+                            "scopes" if parts.len() >= 4 => {
+                                let scope_name = parts[3].to_string();
+                                
+                                if flow_type == "implicit" {
+                                    if let Some(ref mut implicit) = flows.implicit {
+                                        implicit.scopes.insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "password" {
+                                    if let Some(ref mut password) = flows.password {
+                                        password.scopes.insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "clientcredentials"
+                                    || flow_type == "application"
+                                {
+                                    if let Some(ref mut clientCredentials) =
+                                        flows.clientCredentials
                                     {
-                                        if let Some(ref mut clientCredentials) =
-                                            flows.clientCredentials
-                                        {
-                                            clientCredentials
-                                                .scopes
-                                                .insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "authorizationcode"
-                                        || flow_type == "accesscode"
+                                        clientCredentials
+                                            .scopes
+                                            .insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "authorizationcode"
+                                    || flow_type == "accesscode"
+                                {
+                                    if let Some(ref mut authorizationCode) =
+                                        flows.authorizationCode
                                     {
-                                        if let Some(ref mut authorizationCode) =
-                                            flows.authorizationCode
-                                        {
-                                            authorizationCode
-                                                .scopes
-                                                .insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
+                                        authorizationCode
+                                            .scopes
+                                            .insert(scope_name, value.to_string());
+                                        return Ok(());
                                     }
                                 }
                             }
@@ -690,12 +1617,10 @@ impl GoParser {
                 else if let Some(scheme) = api_info.security_definitions.get_mut(value) {
                     if let Some(ref mut flows) = scheme.flows {
                         match property.as_str() {
-                            "authorizationUrl" => {
-                                if flow_type == "implicit" {
-                                    if let Some(ref mut implicit) = flows.implicit {
-                                        implicit.authorizationUrl = Some(value.to_string());
-                                        return Ok(());
-                                    }
+                            "authorizationUrl" if flow_type == "implicit" => {
+                                if let Some(ref mut implicit) = flows.implicit {
+                                    implicit.authorizationUrl = Some(value.to_string());
+                                    return Ok(());
                                 }
                             }
                             "tokenUrl" => {
@@ -751,42 +1676,40 @@ impl GoParser {
                                     }
                                 }
                             }
-                            "scopes" => {
-                                if parts.len() >= 4 {
-                                    let scope_name = parts[3].to_string();
-                                    
-                                    if flow_type == "implicit" {
-                                        if let Some(ref mut implicit) = flows.implicit {
-                                            implicit.scopes.insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "password" {
-                                        if let Some(ref mut password) = flows.password {
-                                            password.scopes.insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "clientcredentials"
-                                        || flow_type == "application"
+                            "scopes" if parts.len() >= 4 => {
+                                let scope_name = parts[3].to_string();
+                                
+                                if flow_type == "implicit" {
+                                    if let Some(ref mut implicit) = flows.implicit {
+                                        implicit.scopes.insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "password" {
+                                    if let Some(ref mut password) = flows.password {
+                                        password.scopes.insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "clientcredentials"
+                                    || flow_type == "application"
+                                {
+                                    if let Some(ref mut clientCredentials) =
+                                        flows.clientCredentials
                                     {
-                                        if let Some(ref mut clientCredentials) =
-                                            flows.clientCredentials
-                                        {
-                                            clientCredentials
-                                                .scopes
-                                                .insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
-                                    } else if flow_type == "authorizationcode"
-                                        || flow_type == "accesscode"
+                                        clientCredentials
+                                            .scopes
+                                            .insert(scope_name, value.to_string());
+                                        return Ok(());
+                                    }
+                                } else if flow_type == "authorizationcode"
+                                    || flow_type == "accesscode"
+                                {
+                                    if let Some(ref mut authorizationCode) =
+                                        flows.authorizationCode
                                     {
-                                        if let Some(ref mut authorizationCode) =
-                                            flows.authorizationCode
-                                        {
-                                            authorizationCode
-                                                .scopes
-                                                .insert(scope_name, value.to_string());
-                                            return Ok(());
-                                        }
+                                        authorizationCode
+                                            .scopes
+                                            .insert(scope_name, value.to_string());
+                                        return Ok(());
                                     }
                                 }
                             }
@@ -813,6 +1736,7 @@ impl GoParser {
                         bearerFormat: None,
                         flows: None,
                         openIdConnectUrl: None,
+                        extensions: HashMap::new(),
                     },
                 );
                 return Ok(());
@@ -829,6 +1753,7 @@ impl GoParser {
                         bearerFormat: None,
                         flows: None,
                         openIdConnectUrl: None,
+                        extensions: HashMap::new(),
                     },
                 );
                 return Ok(());
@@ -849,6 +1774,7 @@ impl GoParser {
                         in_type: None,
                         flows: None,
                         openIdConnectUrl: None,
+                        extensions: HashMap::new(),
                     },
                 );
                 return Ok(());
@@ -859,7 +1785,7 @@ impl GoParser {
                     let mut oauth_flows = OAuthFlows::default();
                     
                     // Create different flows based on the type
-                    match flow_type {
+                    match flow_type.to_lowercase().as_str() {
                         "implicit" => {
                             oauth_flows.implicit = Some(crate::models::OAuthFlow {
                                 scopes: std::collections::HashMap::new(),
@@ -903,6 +1829,7 @@ impl GoParser {
                             bearerFormat: None,
                             flows: Some(oauth_flows),
                             openIdConnectUrl: None,
+                            extensions: HashMap::new(),
                         },
                     );
                     return Ok(());
@@ -926,6 +1853,7 @@ impl GoParser {
                         scheme: None,
                         bearerFormat: None,
                         flows: None,
+                        extensions: HashMap::new(),
                     },
                 );
                 return Ok(());
@@ -970,9 +1898,14 @@ impl GoParser {
         
         debug!("Found {} Go files to parse", all_file_paths.len());
         
+        // Parse every file once; schemas, examples and operations are all built from the ASTs
+        let files: Vec<SourceFile> = all_file_paths
+            .iter()
+            .filter_map(|path| self.parse_go_file(path))
+            .collect();
+
         // Extract examples from structs with import resolution
-        let struct_examples =
-            self.extract_struct_examples_with_imports(&all_file_paths, base_dir.as_ref());
+        let struct_examples = self.extract_struct_examples(&files, base_dir.as_ref());
         debug!(
             "Extracted examples from {} structs (including imported models)",
             struct_examples.len()
@@ -980,90 +1913,177 @@ impl GoParser {
 
         // Collect all model references from operations we find
         let mut referenced_models = HashSet::new();
+        let type_specs = index_type_specs(&files);
         
-        // Now parse all files for operations
-        for file_path in &all_file_paths {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                let mut current_annotations: Vec<Annotation> = Vec::new();
-                
-                for line in content.lines() {
-                    if let Some(captures) = ANNOTATION_REGEX.captures(line) {
-                        let annotation_type_str = captures.get(1).unwrap().as_str();
-                        let attribute = captures.get(2).map(|m| m.as_str().to_string());
-                        let value = captures.get(3).unwrap().as_str().to_string();
-                        
-                        let annotation = Annotation {
-                            annotation_type: AnnotationType::from(annotation_type_str),
-                            attribute,
-                            value,
-                        };
-                        
-                        current_annotations.push(annotation);
-                    } else if line.trim().starts_with("func ") && !current_annotations.is_empty() {
-                        // Function encountered, process the collected annotations
-                        let router_annotation = current_annotations.iter().find(|a| {
-                            matches!(
-                                a.annotation_type,
-                                AnnotationType::Router | AnnotationType::DeprecatedRouter
-                            )
-                        });
+        // Now collect operations from the doc comments of functions
+        for (file, func) in files
+            .iter()
+            .flat_map(|file| file.ast.funcs.iter().map(move |func| (file, func)))
+        {
+            let annotations = self.collect_annotations(func.doc.as_ref(), &file.path);
+            let router_annotation = annotations.iter().find(|a| {
+                matches!(
+                    a.annotation_type,
+                    AnnotationType::Router
+                        | AnnotationType::DeprecatedRouter
+                        | AnnotationType::Webhook
+                )
+            });
+            if router_annotation.is_none() {
+                continue;
+            }
 
-                        if router_annotation.is_some() {
-                            match self.parse_operation_with_examples(
-                                &current_annotations,
-                                &struct_examples,
-                            ) {
-                                Ok(operation) => {
-                                    // Collect schemas from this operation
-                                    self.collect_operation_schema_refs(
-                                        &operation,
-                                        &mut referenced_models,
-                                    );
-                                    operations.push(operation);
-                                }
-                                Err(e) => warn!("Failed to parse operation: {}", e),
-                            }
-                        }
-                        
-                        current_annotations.clear();
+            match self.parse_operation_with_examples(&annotations, &struct_examples, &type_specs) {
+                Ok(handler_operations) => {
+                    for mut operation in handler_operations {
+                        self.apply_func_doc(func, &mut operation.operation);
+                        // Collect schemas from this operation
+                        self.collect_operation_schema_refs(&operation.operation, &mut referenced_models);
+                        operations.push(operation);
                     }
                 }
+                Err(e) => {
+                    warn!("Failed to parse operation {}: {}", func.name, e);
+                    self.problems.borrow_mut().push(e);
+                }
+            }
+        }
+
+        // An operationId used by several operations, from one handler's routes or an
+        // @ID repeated across handlers, gets a numeric suffix on the later ones
+        let mut used_ids = HashSet::new();
+        for operation in &mut operations {
+            let Some(operation_id) = operation.operation.operationId.clone() else {
+                continue;
+            };
+            let mut unique_id = operation_id.clone();
+            let mut suffix = 2;
+            while !used_ids.insert(unique_id.clone()) {
+                unique_id = format!("{}_{}", operation_id, suffix);
+                suffix += 1;
             }
+            operation.operation.operationId = Some(unique_id);
         }
 
         // Add common response types
-        referenced_models.insert("response.ApiResponse".to_string());
-        referenced_models.insert("response.Response".to_string());
-        referenced_models.insert("response.OpenApiResponse".to_string());
-        referenced_models.insert("response.OpenApiErrorNonSnap".to_string());
+        referenced_models.extend(COMMON_SCHEMA_NAMES.iter().map(|name| name.to_string()));
 
         debug!("Found {} referenced models", referenced_models.len());
 
         // Extract schemas for the referenced models
-        let struct_schemas = self.extract_referenced_schemas(&all_file_paths, &referenced_models);
+        let mut struct_schemas =
+            self.extract_referenced_schemas(&files, &referenced_models);
         debug!(
             "Extracted schemas for {} referenced models",
             struct_schemas.len()
         );
+
+        // Give generic instantiations readable names, e.g. `Page[models.User]` -> `Page-models_User`
+        self.rename_generic_schemas(&mut operations, &mut struct_schemas);
         
         Ok((operations, struct_schemas))
     }
 
+    // Parse one comment line (without its `//` marker) as an annotation
+    fn parse_annotation(&self, comment_text: &str) -> Option<Annotation> {
+        let captures = ANNOTATION_REGEX.captures(comment_text)?;
+        Some(Annotation {
+            annotation_type: AnnotationType::from(captures.get(1).unwrap().as_str()),
+            attribute: captures.get(2).map(|m| m.as_str().to_string()),
+            value: captures.get(3).map_or("", |m| m.as_str()).trim_end().to_string(),
+            location: None,
+        })
+    }
+
+    // Use the plain text of a handler's doc comment as the operation description,
+    // and its first sentence as the summary, when the annotations give none
+    fn apply_func_doc(&self, func: &FuncDecl, operation: &mut Operation) {
+        let Some(doc) = &func.doc else {
+            return;
+        };
+
+        // swag's `// GetUser godoc` marker line is not documentation
+        let godoc_marker = format!("{} godoc", func.name);
+        let text = doc
+            .text()
+            .lines()
+            .filter(|line| !ANNOTATION_REGEX.is_match(line) && line.trim() != godoc_marker)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        if is_deprecation_notice(text) {
+            operation.deprecated = Some(true);
+        }
+
+        if operation.summary.is_none() {
+            operation.summary = Some(first_sentence(text));
+        }
+        if operation.description.is_none() {
+            operation.description = Some(text.to_string());
+        }
+    }
+
+    // Describe a schema with a doc comment; a `Deprecated:` paragraph marks it deprecated
+    fn apply_doc_comment(&self, doc: Option<&CommentGroup>, schema: &mut Schema) {
+        let Some(text) = doc.map(CommentGroup::text).filter(|text| !text.is_empty()) else {
+            return;
+        };
+
+        if is_deprecation_notice(&text) {
+            schema.deprecated = Some(true);
+        }
+        schema.description = Some(text);
+    }
+
+    // Annotations in a doc comment, in source order, with their locations in `path`
+    fn collect_annotations(&self, doc: Option<&CommentGroup>, path: &Path) -> Vec<Annotation> {
+        let mut annotations = Vec::new();
+        for comment in doc.iter().flat_map(|group| &group.comments) {
+            // The comment marker starts the first line; later lines of a block comment are whole source lines
+            let marker = &comment.text[..2];
+            let first_line_prefix = format!("{}{}", " ".repeat(comment.pos.column - 1), marker);
+            for (index, line) in comment.content().lines().enumerate() {
+                let Some(mut annotation) = self.parse_annotation(line) else {
+                    continue;
+                };
+                let source_line = if index == 0 {
+                    format!("{}{}", first_line_prefix, line)
+                } else {
+                    line.to_string()
+                };
+                let prefix_len = if index == 0 { first_line_prefix.chars().count() } else { 0 };
+                let at = line.find('@').unwrap_or(0);
+                annotation.location = Some(SourceLocation {
+                    file: path.to_path_buf(),
+                    line: comment.pos.line + index,
+                    column: prefix_len + line[..at].chars().count() + 1,
+                    source_line,
+                });
+                annotations.push(annotation);
+            }
+        }
+        annotations
+    }
+
     // Extract schema references from an operation
     fn collect_operation_schema_refs(
         &self,
-        operation: &ParsedOperation,
+        operation: &Operation,
         referenced_models: &mut HashSet<String>,
     ) {
         // Check parameters
-        for param in &operation.operation.parameters {
+        for param in &operation.parameters {
             if let Some(schema) = &param.schema {
                 self.collect_schema_references(schema, referenced_models);
             }
         }
 
         // Check request body
-        if let Some(req_body) = &operation.operation.requestBody {
+        if let Some(req_body) = &operation.requestBody {
             for media_type in req_body.content.values() {
                 if let Some(schema) = &media_type.schema {
                     self.collect_schema_references(schema, referenced_models);
@@ -1072,7 +2092,7 @@ impl GoParser {
         }
 
         // Check responses
-        for response in operation.operation.responses.values() {
+        for response in operation.responses.values() {
             for media_type in response.content.values() {
                 if let Some(schema) = &media_type.schema {
                     self.collect_schema_references(schema, referenced_models);
@@ -1080,23 +2100,10 @@ impl GoParser {
             }
         }
 
-        // Extract generic parameterized types like ApiResponse{data=User}
-        let mut generics_to_process = Vec::new();
-
-        for model in referenced_models.iter() {
-            if model.contains('{') && model.contains('}') {
-                generics_to_process.push(model.clone());
-            }
-        }
-
-        for generic_ref in &generics_to_process {
-            // Handle patterns like "response.ApiResponse{data=userModel.User}"
-            if generic_ref.contains("=") {
-                let parts: Vec<&str> = generic_ref.split('=').collect();
-                if parts.len() >= 2 {
-                    let inner_type = parts[1].trim().trim_end_matches('}');
-                    referenced_models.insert(inner_type.to_string());
-                }
+        // Check callback operations
+        for path_item in operation.callbacks.values().flat_map(|c| c.values()) {
+            for callback_operation in path_item.operations() {
+                self.collect_operation_schema_refs(callback_operation, referenced_models);
             }
         }
     }
@@ -1156,77 +2163,8 @@ impl GoParser {
         }
     }
 
-    // Original collect_referenced_models method renamed to avoid conflicts
-    #[allow(dead_code)]
-    fn collect_model_references_from_annotations(&self, file_paths: &[PathBuf]) -> HashSet<String> {
-        // Original implementation...
-        let mut referenced_models = HashSet::new();
-
-        // Patterns to match model references in annotations
-        let param_regex = Regex::new(r"@Param\s+\w+\s+body\s+([a-zA-Z0-9_.]+)").unwrap();
-        let request_body_regex =
-            Regex::new(r"@RequestBody\s+.*\{object\}\s+([a-zA-Z0-9_.]+)").unwrap();
-        // Make the response regex more flexible to handle various whitespace patterns
-        let response_regex = Regex::new(
-            r"@(?:Success|Failure|Response)\s+\d+\s+\{(?:object|array)\}\s*([a-zA-Z0-9_.]+)",
-        )
-        .unwrap();
-
-        for file_path in file_paths {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // Find all model references in @Param annotations
-                for cap in param_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
-
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
-                    }
-                }
-
-                // Find all model references in @RequestBody annotations
-                for cap in request_body_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
-
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
-                    }
-                }
-
-                // Find all model references in @Success, @Failure, and @Response annotations
-                for cap in response_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
-
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        debug!("Found referenced models: {:?}", referenced_models);
-        referenced_models
-    }
-    
-    // Helper method to recursively collect Go files in a directory and its subdirectories
-    fn collect_go_files_recursively(
+    /// Recursively collect the Go files in a directory and its subdirectories
+    pub fn collect_go_files_recursively(
         &self,
         dir_path: &Path,
         excluded_dirs: &[impl AsRef<Path>],
@@ -1278,15 +2216,133 @@ impl GoParser {
         }
     }
     
+    // One operation per `@Router`/`@Webhook` line of a handler, sharing everything else
     fn parse_operation_with_examples(
+        &self,
+        annotations: &[Annotation],
+        struct_examples: &HashMap<String, HashMap<String, serde_json::Value>>,
+        type_specs: &TypeIndex,
+    ) -> Result<Vec<ParsedOperation>, ParserError> {
+        let (operation, routes) = self.build_operation(annotations, struct_examples, type_specs)?;
+
+        let operations = routes
+            .into_iter()
+            .map(|route| {
+                let mut operation = operation.clone();
+                if route.deprecated {
+                    operation.deprecated = Some(true);
+                }
+                if !route.webhook {
+                    self.reconcile_path_parameters(&route, &mut operation);
+                }
+
+                if operation.operationId.is_none() {
+                    operation.operationId = Some(self.default_operation_id(&route));
+                }
+
+                ParsedOperation {
+                    path: route.path,
+                    method: route.method,
+                    operation,
+                    webhook: route.webhook,
+                }
+            })
+            .collect();
+        Ok(operations)
+    }
+
+    // Make the path parameters of an operation match its route template: parameters
+    // missing from the template are dropped, template parameters without a
+    // `@Param ... path` are added as required strings
+    fn reconcile_path_parameters(&self, route: &Route, operation: &mut Operation) {
+        let template_params: Vec<&str> = PATH_PARAM_REGEX
+            .captures_iter(&route.path)
+            .map(|captures| captures.get(1).unwrap().as_str())
+            .collect();
+
+        operation.parameters.retain(|parameter| {
+            if parameter.in_type != "path" || template_params.contains(&parameter.name.as_str()) {
+                return true;
+            }
+            self.report(
+                ParserError::ParameterParseError(format!(
+                    "path parameter {} does not appear in route {}; dropping it",
+                    parameter.name, route.path
+                ))
+                .at(route.location.as_ref()),
+            );
+            false
+        });
+
+        for name in template_params {
+            let index = match operation
+                .parameters
+                .iter()
+                .position(|p| p.in_type == "path" && p.name == name)
+            {
+                Some(index) => index,
+                None => {
+                    self.report(
+                        ParserError::ParameterParseError(format!(
+                            "route {} has no @Param for path parameter {}; assuming a string",
+                            route.path, name
+                        ))
+                        .at(route.location.as_ref()),
+                    );
+                    operation.parameters.push(Parameter {
+                        name: name.to_string(),
+                        in_type: "path".to_string(),
+                        required: Some(true),
+                        schema: Some(Schema {
+                            type_: Some(serde_json::Value::String("string".to_string())),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    operation.parameters.len() - 1
+                }
+            };
+
+            if let Some(pattern) = route.patterns.get(name) {
+                let schema = operation.parameters[index].schema.get_or_insert_with(Schema::default);
+                if schema.pattern.is_none() {
+                    schema.pattern = Some(pattern.clone());
+                }
+            }
+        }
+    }
+
+    // Operation IDs such as `get_users_id` for routes without an @ID
+    fn default_operation_id(&self, route: &Route) -> String {
+        let method = &route.method;
+        // `/orders/{id}` gives `get_orders_id`
+        let path = route.path.replace(['{', '}'], "");
+        match method.as_str() {
+            _ if route.webhook => format!("{}_{}", method, path),
+            "get" => format!("get{}", path.replace("/", "_")),
+            "post" => format!("post{}", path.replace("/", "_")),
+            "put" => format!("put{}", path.replace("/", "_")),
+            "delete" => format!("delete{}", path.replace("/", "_")),
+            "patch" => format!("patch{}", path.replace("/", "_")),
+            "head" => format!("head{}", path.replace("/", "_")),
+            "options" => format!("options{}", path.replace("/", "_")),
+            _ => format!("{}_{}", method, path.replace("/", "_")),
+        }
+    }
+
+    // Build the operation described by a handler's annotations, and the routes it serves
+    fn build_operation(
         &self, 
         annotations: &[Annotation], 
         struct_examples: &HashMap<String, HashMap<String, serde_json::Value>>,
-    ) -> Result<ParsedOperation, ParserError> {
+        type_specs: &TypeIndex,
+    ) -> Result<(Operation, Vec<Route>), ParserError> {
         let mut operation = Operation::default();
-        let mut path = String::new();
-        let mut method = String::new();
+        let mut routes = Vec::new();
         let mut request_body_schema_ref: Option<String> = None;
+        let mut response_headers = Vec::new();
+        let mut callbacks: Vec<CallbackDecl> = Vec::new();
+        let mut form_params = Vec::new();
         
         for annotation in annotations {
             match &annotation.annotation_type {
@@ -1310,18 +2366,36 @@ impl GoParser {
                 }
                 AnnotationType::Router | AnnotationType::DeprecatedRouter => {
                     if let Some(captures) = ROUTER_REGEX.captures(&annotation.value) {
-                        path = format!("/{}", captures.get(1).unwrap().as_str());
-                        method = captures.get(2).unwrap().as_str().to_lowercase();
-                        
-                        // Mark as deprecated if using deprecated router
-                        if let AnnotationType::DeprecatedRouter = annotation.annotation_type {
-                            operation.deprecated = Some(true);
-                        }
+                        let (path, patterns) =
+                            normalize_route_path(&format!("/{}", captures.get(1).unwrap().as_str()));
+                        routes.push(Route {
+                            path,
+                            method: captures.get(2).unwrap().as_str().to_lowercase(),
+                            patterns,
+                            // Only this route is deprecated when using a deprecated router
+                            deprecated: annotation.annotation_type == AnnotationType::DeprecatedRouter,
+                            webhook: false,
+                            location: annotation.location.clone(),
+                        });
                     } else {
-                        return Err(ParserError::RouterParseError(format!(
-                            "Invalid router format: {}",
-                            annotation.value
-                        )));
+                        return Err(ParserError::RouterParseError(annotation.value.clone())
+                            .at(annotation.location.as_ref()));
+                    }
+                }
+                AnnotationType::Webhook => {
+                    // Webhooks are named rather than routed, and live outside `paths`
+                    if let Some(captures) = WEBHOOK_REGEX.captures(annotation.value.trim()) {
+                        routes.push(Route {
+                            path: captures[1].to_string(),
+                            method: captures[2].to_lowercase(),
+                            patterns: HashMap::new(),
+                            deprecated: false,
+                            webhook: true,
+                            location: annotation.location.clone(),
+                        });
+                    } else {
+                        return Err(ParserError::WebhookParseError(annotation.value.clone())
+                            .at(annotation.location.as_ref()));
                     }
                 }
                 AnnotationType::Accept => {
@@ -1361,7 +2435,7 @@ impl GoParser {
                     });
                 }
                 AnnotationType::Param => {
-                    match self.parse_parameter(&annotation.value) {
+                    match self.parse_parameter(annotation) {
                         Ok(parameter) => {
                             // For body parameters, extract schema ref for request body
                             if parameter.in_type == "body" {
@@ -1401,8 +2475,10 @@ impl GoParser {
 
                                         // Add to all content types or create application/json if none
                                         if request_body.content.is_empty() {
-                                            let mut media_type = MediaType::default();
-                                            media_type.schema = Some(schema);
+                                            let media_type = MediaType {
+                                                schema: Some(schema),
+                                                ..Default::default()
+                                            };
                                             request_body
                                                 .content
                                                 .insert("application/json".to_string(), media_type);
@@ -1468,13 +2544,18 @@ impl GoParser {
                             }
                             
                             // Add parameter to the operation (except 'body' parameters in OpenAPI 3)
-                            if parameter.in_type != "body" {
+                            if parameter.in_type == "formData" {
+                                // Form fields become the request body once all are known
+                                form_params.push(parameter);
+                            } else if let Some(expanded) =
+                                self.expand_struct_parameter(&parameter, type_specs)
+                            {
+                                operation.parameters.extend(expanded);
+                            } else if parameter.in_type != "body" {
                                 operation.parameters.push(parameter);
                             }
                         }
-                        Err(e) => {
-                            warn!("Failed to parse parameter: {}", e);
-                        }
+                        Err(e) => self.report(e.at(annotation.location.as_ref())),
                     }
                 }
                 AnnotationType::RequestBody => {
@@ -1612,13 +2693,93 @@ impl GoParser {
                                     }
                                 }
                             }
-                            
-                            operation.responses.insert(response.code.clone(), response);
+
+                            // `@Success 200,201 ...` declares the same response for each code
+                            for code in response.code.split(',') {
+                                operation.responses.insert(
+                                    code.to_string(),
+                                    Response {
+                                        code: code.to_string(),
+                                        ..response.clone()
+                                    },
+                                );
+                            }
+                        }
+                        Err(e) => self.report(e.at(annotation.location.as_ref())),
+                    }
+                }
+                AnnotationType::Deprecated => {
+                    operation.deprecated = Some(true);
+                    // Sunset date and replacement are extensions API gateways understand
+                    match annotation.attribute.as_deref() {
+                        None => {}
+                        Some("sunset") => {
+                            operation
+                                .extensions
+                                .insert("x-sunset".to_string(), annotation.value.clone().into());
+                        }
+                        Some("replacement") => {
+                            operation
+                                .extensions
+                                .insert("x-replaced-by".to_string(), annotation.value.clone().into());
+                        }
+                        Some(attribute) => self.report(
+                            ParserError::UnknownAttribute(format!("@Deprecated.{}", attribute))
+                                .at(annotation.location.as_ref()),
+                        ),
+                    }
+                }
+                AnnotationType::Extension(name) => {
+                    operation
+                        .extensions
+                        .insert(name.clone(), parse_extension_value(&annotation.value));
+                }
+                AnnotationType::Callback => match &annotation.attribute {
+                    None => match self.parse_callback(annotation) {
+                        Ok(callback) => callbacks.push(callback),
+                        Err(e) => self.report(e.at(annotation.location.as_ref())),
+                    },
+                    Some(attribute) => {
+                        // `@Callback.success onPaymentEvent 200 ...` is `@Success 200 ...` on the callback
+                        let (name, value) = annotation
+                            .value
+                            .split_once(char::is_whitespace)
+                            .unwrap_or((annotation.value.as_str(), ""));
+                        let (annotation_type, sub_attribute) = match attribute.split_once('.') {
+                            Some((annotation_type, sub_attribute)) => {
+                                (annotation_type, Some(sub_attribute.to_string()))
+                            }
+                            None => (attribute.as_str(), None),
+                        };
+                        let callback_annotation = Annotation {
+                            annotation_type: AnnotationType::from(annotation_type),
+                            attribute: sub_attribute,
+                            value: value.trim().to_string(),
+                            location: annotation.location.clone(),
+                        };
+
+                        let mut matched = false;
+                        for callback in callbacks.iter_mut().filter(|c| c.name == name) {
+                            callback.annotations.push(callback_annotation.clone());
+                            matched = true;
                         }
-                        Err(e) => {
-                            warn!("Failed to parse response: {}", e);
+                        if !matched {
+                            self.report(
+                                ParserError::CallbackParseError(format!(
+                                    "{} refers to undeclared callback {}",
+                                    attribute, name
+                                ))
+                                .at(annotation.location.as_ref()),
+                            );
                         }
                     }
+                },
+                AnnotationType::Header => {
+                    // Headers are attached once all responses are known
+                    match self.parse_header(&annotation.value) {
+                        Ok(header) => response_headers.push((header, annotation.location.clone())),
+                        Err(e) => self.report(e.at(annotation.location.as_ref())),
+                    }
                 }
                 AnnotationType::Security => {
                     let mut security_requirement = HashMap::new();
@@ -1630,14 +2791,29 @@ impl GoParser {
                             parts.iter().skip(1).map(|s| s.to_string()).collect();
                         security_requirement.insert(security_name.to_string(), scopes);
                         operation.security.push(security_requirement);
+                    } else {
+                        let error = ParserError::SecurityParseError(
+                            "missing security scheme name".to_string(),
+                        );
+                        self.report(error.at(annotation.location.as_ref()));
                     }
                 }
+                AnnotationType::Unknown(name) => {
+                    self.report(
+                        ParserError::UnknownAnnotation(name.clone()).at(annotation.location.as_ref()),
+                    );
+                }
                 _ => {
                     // Other annotations are not directly relevant to operations
                 }
             }
         }
         
+        // OpenAPI 3 has no formData parameters; they are the fields of a form request body
+        if !form_params.is_empty() {
+            operation.requestBody = Some(self.form_request_body(&form_params, &operation.consumes));
+        }
+
         // Add examples to request body if we have a schema reference
         if let Some(model_name) = request_body_schema_ref {
             // Try to find examples for this model or split the model name if it has a package prefix
@@ -1667,12 +2843,14 @@ impl GoParser {
                     // Add example to all content types or add application/json if none
                     if let Some(ref mut request_body) = operation.requestBody {
                         if request_body.content.is_empty() {
-                            let mut media_type = MediaType::default();
-                            media_type.example = Some(example_value.clone());
-                            media_type.schema = Some(Schema {
-                                ref_: Some(format!("#/components/schemas/{}", model_name)),
+                            let media_type = MediaType {
+                                example: Some(example_value.clone()),
+                                schema: Some(Schema {
+                                    ref_: Some(format!("#/components/schemas/{}", model_name)),
+                                    ..Default::default()
+                                }),
                                 ..Default::default()
-                            });
+                            };
                                 request_body
                                     .content
                                     .insert("application/json".to_string(), media_type);
@@ -1697,21 +2875,56 @@ impl GoParser {
             }
         }
         
-        // Generate operation ID if not provided
-        if operation.operationId.is_none() {
-            let operation_id = match method.as_str() {
-                "get" => format!("get{}", path.replace("/", "_")),
-                "post" => format!("post{}", path.replace("/", "_")),
-                "put" => format!("put{}", path.replace("/", "_")),
-                "delete" => format!("delete{}", path.replace("/", "_")),
-                "patch" => format!("patch{}", path.replace("/", "_")),
-                "head" => format!("head{}", path.replace("/", "_")),
-                "options" => format!("options{}", path.replace("/", "_")),
-                _ => format!("{}_{}", method, path.replace("/", "_")),
-            };
-            operation.operationId = Some(operation_id);
+        // Routes named in warnings about the handler
+        let path = routes
+            .iter()
+            .map(|route| route.path.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // `all` adds a header to every response, other codes to their own response
+        for ((codes, name, header), location) in response_headers {
+            for code in codes {
+                if code.eq_ignore_ascii_case("all") {
+                    for response in operation.responses.values_mut() {
+                        response.headers.insert(name.clone(), header.clone());
+                    }
+                } else if let Some(response) = operation.responses.get_mut(&code) {
+                    response.headers.insert(name.clone(), header.clone());
+                } else {
+                    self.report(
+                        ParserError::HeaderParseError(format!(
+                            "Header {} refers to undeclared response {} on {}",
+                            name, code, path
+                        ))
+                        .at(location.as_ref()),
+                    );
+                }
+            }
         }
-        
+
+        // Callback operations are parsed like any other operation, minus the router
+        for callback in callbacks {
+            let (callback_operation, _) =
+                self.build_operation(&callback.annotations, struct_examples, type_specs)?;
+
+            let path_item = operation
+                .callbacks
+                .entry(callback.name)
+                .or_default()
+                .entry(callback.expression)
+                .or_default();
+            if !path_item.set_operation(&callback.method, callback_operation) {
+                self.report(
+                    ParserError::CallbackParseError(format!(
+                        "Unknown callback method {} on {}",
+                        callback.method, path
+                    ))
+                    .at(callback.location.as_ref()),
+                );
+            }
+        }
+
         // If we have no produces but have responses, add a default content type
         if operation.produces.is_empty() && !operation.responses.is_empty() {
             operation.produces.push("application/json".to_string());
@@ -1726,74 +2939,263 @@ impl GoParser {
             }
         }
         
-        Ok(ParsedOperation {
-            path,
-            method,
-            operation,
-        })
+        Ok((operation, routes))
     }
     
-    // Add a new method to extract imports from a file
-    fn extract_imports(&self, file_content: &str) -> Vec<ImportInfo> {
-        let mut imports = Vec::new();
-        let import_regex = Regex::new(r#"import\s+\(\s*((?:[^()]*\n)+)\s*\)"#).unwrap();
-        let single_import_regex =
-            Regex::new(r#"import\s+(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
-        let alias_import_regex = Regex::new(r#"\s*(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
-
-        if let Some(caps) = import_regex.captures(file_content) {
-            if let Some(import_block) = caps.get(1) {
-                for line in import_block.as_str().lines() {
-                    if let Some(m) = alias_import_regex.captures(line) {
-                        let alias = m.get(1).map_or_else(
-                            || {
-                                // If no alias, use the last part of the path
-                                let path = m.get(2).unwrap().as_str();
-                                path.split('/').next_back().unwrap_or(path).to_string()
-                            },
-                            |a| a.as_str().to_string(),
-                        );
-                        let path = m.get(2).unwrap().as_str().to_string();
-                        imports.push(ImportInfo {
-                            alias,
-                            path,
-                            file_path: None,
-                        });
-                    }
-                }
-            }
-        } else {
-            // Check for single line imports
-            for caps in single_import_regex.captures_iter(file_content) {
-                let alias = caps.get(1).map_or_else(
-                    || {
-                        // If no alias, use the last part of the path
-                        let path = caps.get(2).unwrap().as_str();
-                        path.split('/').next_back().unwrap_or(path).to_string()
-                    },
-                    |a| a.as_str().to_string(),
-                );
-                let path = caps.get(2).unwrap().as_str().to_string();
-                imports.push(ImportInfo {
-                    alias,
-                    path,
-                    file_path: None,
-                });
-            }
+    // Expand a query, header or path parameter typed as a struct into one parameter
+    // per field, as swag does; None when the parameter is not struct-typed
+    fn expand_struct_parameter(
+        &self,
+        parameter: &Parameter,
+        type_specs: &TypeIndex,
+    ) -> Option<Vec<Parameter>> {
+        if !matches!(parameter.in_type.as_str(), "query" | "header" | "path") {
+            return None;
         }
+        let model_name = parameter
+            .schema
+            .as_ref()?
+            .ref_
+            .as_deref()?
+            .strip_prefix("#/components/schemas/")?;
+        let (package, name) = match model_name.rsplit_once('.') {
+            Some((package, name)) => (Some(package), name),
+            None => (None, model_name),
+        };
+        let spec = lookup_type_spec(type_specs, package, name)?;
+        let TypeExpr::Struct(fields) = &spec.ty else {
+            return None;
+        };
 
-        debug!("Extracted {} imports", imports.len());
-        imports
+        let mut parameters = Vec::new();
+        self.collect_field_parameters(parameter, fields, type_specs, &mut parameters);
+        debug!(
+            "Expanded parameter {} of type {} into {} parameters",
+            parameter.name,
+            model_name,
+            parameters.len()
+        );
+        Some(parameters)
     }
 
-    // Add a method to resolve import paths to actual files
-    fn resolve_import_paths(
+    // Parameters for the fields of a struct, including the fields of embedded structs
+    fn collect_field_parameters(
         &self,
-        imports: &mut Vec<ImportInfo>,
-        base_dir: &Path,
-        go_mod_path: Option<&Path>,
-    ) -> Result<()> {
-        let go_path = std::env::var("GOPATH").unwrap_or_else(|_| "/tmp".to_string());
+        parameter: &Parameter,
+        fields: &[go_ast::Field],
+        type_specs: &TypeIndex,
+        parameters: &mut Vec<Parameter>,
+    ) {
+        for field in fields.iter().flat_map(StructField::from_ast) {
+            if field.is_promoted() {
+                let mut base = &field.ty;
+                while let TypeExpr::Pointer(inner) = base {
+                    base = inner;
+                }
+                if let TypeExpr::Named { package, name, .. } = base {
+                    if let Some(TypeExpr::Struct(embedded)) =
+                        lookup_type_spec(type_specs, package.as_deref(), name).map(|spec| &spec.ty)
+                    {
+                        self.collect_field_parameters(parameter, embedded, type_specs, parameters);
+                    }
+                }
+                continue;
+            }
+            let Some(name) = field.param_name(&parameter.in_type) else {
+                continue;
+            };
+            if self.is_skipped_type(&field.type_name()) {
+                continue;
+            }
+
+            let mut schema = self.field_type_schema(&field.ty, &mut HashSet::new());
+            self.apply_validation_rules(&field.validation_rules(), &mut schema);
+            self.apply_doc_comment(field.doc.as_ref(), &mut schema);
+            let example = field.tag.get("example").map(|value| coerce_param_value(value, &schema));
+
+            parameters.push(Parameter {
+                name,
+                in_type: parameter.in_type.clone(),
+                description: schema.description.take(),
+                // Path parameters are always required
+                required: Some(parameter.in_type == "path" || field.has_required_rule()),
+                deprecated: schema.deprecated.take(),
+                schema: Some(schema),
+                example,
+                ..Default::default()
+            });
+        }
+    }
+
+    // A request body with one object schema for all `formData` parameters. Files need
+    // multipart/form-data; other forms default to application/x-www-form-urlencoded
+    // unless @Accept names a form type
+    fn form_request_body(&self, params: &[Parameter], consumes: &[String]) -> RequestBody {
+        let mut schema = Schema {
+            type_: Some(serde_json::Value::String("object".to_string())),
+            ..Default::default()
+        };
+        let mut encoding = HashMap::new();
+        let mut required = Vec::new();
+        let mut has_file = false;
+
+        for param in params {
+            let mut property = param.schema.clone().unwrap_or_default();
+            let is_file = file_to_binary(&mut property);
+            has_file |= is_file;
+            property.description = param.description.clone();
+            if property.example.is_none() {
+                property.example = param.example.clone();
+            }
+
+            // Each multipart part carries its own content type
+            let content_type = if is_file {
+                Some("application/octet-stream")
+            } else if property.ref_.is_some() {
+                Some("application/json")
+            } else {
+                None
+            };
+            if let Some(content_type) = content_type {
+                encoding.insert(
+                    param.name.clone(),
+                    Encoding {
+                        contentType: Some(content_type.to_string()),
+                        ..Default::default()
+                    },
+                );
+            }
+
+            if param.required == Some(true) {
+                required.push(param.name.clone());
+            }
+            schema.properties.insert(param.name.clone(), Box::new(property));
+        }
+        if !required.is_empty() {
+            schema.required = Some(required);
+        }
+
+        let mut content_types: Vec<&str> = consumes
+            .iter()
+            .map(String::as_str)
+            .filter(|c| matches!(*c, "multipart/form-data" | "application/x-www-form-urlencoded"))
+            .collect();
+        if content_types.is_empty() {
+            content_types.push(if has_file {
+                "multipart/form-data"
+            } else {
+                "application/x-www-form-urlencoded"
+            });
+        }
+
+        let content = content_types
+            .into_iter()
+            .map(|content_type| {
+                let media_type = MediaType {
+                    schema: Some(schema.clone()),
+                    // Encoding only applies to multipart parts here
+                    encoding: if content_type == "multipart/form-data" {
+                        encoding.clone()
+                    } else {
+                        HashMap::new()
+                    },
+                    ..Default::default()
+                };
+                (content_type.to_string(), media_type)
+            })
+            .collect();
+
+        RequestBody {
+            description: None,
+            content,
+            required: Some(params.iter().any(|p| p.required == Some(true))),
+        }
+    }
+
+    // Parse `name expression [method] [model] ["description"]`; the model is the
+    // request body the API sends to the callback URL
+    fn parse_callback(&self, annotation: &Annotation) -> Result<CallbackDecl, ParserError> {
+        let s = &annotation.value;
+        let captures = CALLBACK_REGEX
+            .captures(s.trim())
+            .ok_or_else(|| ParserError::CallbackParseError(s.to_string()))?;
+
+        let mut annotations = Vec::new();
+        if let Some(model) = captures.get(4) {
+            let description = captures
+                .get(5)
+                .map(|m| m.as_str().trim())
+                .filter(|d| !d.is_empty())
+                .unwrap_or("payload");
+            annotations.push(Annotation {
+                annotation_type: AnnotationType::Param,
+                attribute: None,
+                value: format!("payload body {} true \"{}\"", model.as_str(), description),
+                location: annotation.location.clone(),
+            });
+        }
+
+        Ok(CallbackDecl {
+            name: captures[1].to_string(),
+            expression: captures[2].to_string(),
+            method: captures[3].to_lowercase(),
+            annotations,
+            location: annotation.location.clone(),
+        })
+    }
+
+    // Read and parse a Go source file, reporting syntax errors as warnings
+    fn parse_go_file(&self, path: &Path) -> Option<SourceFile> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to read {}: {}", path.display(), e);
+                return None;
+            }
+        };
+
+        match go_ast::parse_file(&content) {
+            Ok(ast) => {
+                for error in &ast.errors {
+                    warn!("{}:{}; skipping declaration", path.display(), error);
+                }
+                Some(SourceFile {
+                    path: path.to_path_buf(),
+                    source: content,
+                    ast,
+                })
+            }
+            Err(e) => {
+                warn!("{}:{}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    // Imports of a parsed file, keyed by the name the package is referred to by
+    fn import_infos(&self, file: &GoFile) -> Vec<ImportInfo> {
+        let imports: Vec<ImportInfo> = file
+            .imports
+            .iter()
+            .map(|import| ImportInfo {
+                alias: import.package_name().to_string(),
+                path: import.path.clone(),
+                file_path: None,
+            })
+            .collect();
+
+        debug!("Extracted {} imports", imports.len());
+        imports
+    }
+
+    // Add a method to resolve import paths to actual files
+    fn resolve_import_paths(
+        &self,
+        imports: &mut [ImportInfo],
+        base_dir: &Path,
+        go_mod_path: Option<&Path>,
+    ) -> Result<()> {
+        let go_path = std::env::var("GOPATH").unwrap_or_else(|_| "/tmp".to_string());
         let go_modules_cache = PathBuf::from(go_path.clone()).join("pkg/mod");
 
         // First try to find go.mod file if not provided
@@ -1888,15 +3290,13 @@ impl GoParser {
                 for entry in std::fs::read_dir(&go_modules_cache)
                     .ok()
                     .into_iter()
-                    .flatten()
+                    .flatten().flatten()
                 {
-                    if let Ok(entry) = entry {
-                        let path = entry.path();
-                        if path.is_dir() {
-                            let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                            if dir_name.starts_with(&import.path) {
-                                potential_paths.push(path);
-                            }
+                    let path = entry.path();
+                    if path.is_dir() {
+                        let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                        if dir_name.starts_with(&import.path) {
+                            potential_paths.push(path);
                         }
                     }
                 }
@@ -1933,52 +3333,26 @@ impl GoParser {
         Ok(())
     }
 
-    // Add a method to find model files by name, recursively searching through imports
-    fn find_model_file(
-        &self,
-        model_name: &str,
-        imports: &[ImportInfo],
-        file_content: &str,
-        visited: &mut HashSet<String>,
-    ) -> Option<(PathBuf, String)> {
-        // First, check if the model is defined in the current file
-        let model_regex = Regex::new(&format!(r"type\s+{}\s+struct", model_name)).unwrap();
-        if model_regex.is_match(file_content) {
-            return None; // Model is in the current file, no need to find an external file
-        }
-
-        // Look through imports to find the model
-        for import in imports {
-            // Skip if we've already visited this import to prevent cycles
-            if visited.contains(&import.path) {
+    // Find the fields of a struct declared in an imported package
+    fn find_imported_struct(&self, model_name: &str, import: &ImportInfo) -> Option<Vec<go_ast::Field>> {
+        let package_dir = import.file_path.as_ref().filter(|path| path.is_dir())?;
+
+        for entry in std::fs::read_dir(package_dir).ok().into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "go") {
                 continue;
             }
-            visited.insert(import.path.clone());
-
-            // Check if the model reference includes this import's alias
-            let ref_pattern = format!(r"{}\.\s*{}", import.alias, model_name);
-            let ref_regex = Regex::new(&ref_pattern).unwrap();
-
-            if ref_regex.is_match(file_content) {
-                // Found a reference, now check if we can find the model file
-                if let Some(file_path) = &import.file_path {
-                    // Try to find Go files in this directory
-                    if file_path.is_dir() {
-                        for entry in std::fs::read_dir(file_path).ok().into_iter().flatten() {
-                            if let Ok(entry) = entry {
-                                let path = entry.path();
-                                if path.extension().is_some_and(|ext| ext == "go") {
-                                    if let Ok(content) = std::fs::read_to_string(&path) {
-                                        if model_regex.is_match(&content) {
-                                            debug!("Found model {} in file {:?}", model_name, path);
-                                            return Some((path, content));
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+            let Some(file) = self.parse_go_file(&path) else {
+                continue;
+            };
+
+            let fields = file.ast.types.into_iter().find_map(|spec| match spec.ty {
+                TypeExpr::Struct(fields) if spec.name == model_name => Some(fields),
+                _ => None,
+            });
+            if fields.is_some() {
+                debug!("Found model {} in file {:?}", model_name, path);
+                return fields;
             }
         }
 
@@ -1986,201 +3360,270 @@ impl GoParser {
     }
 
     // Enhance the extract_struct_examples method to handle models from imports
+    #[allow(dead_code)]
     pub fn extract_struct_examples_with_imports(
         &self,
         file_paths: &[PathBuf],
         base_dir: impl AsRef<Path>,
+    ) -> HashMap<String, HashMap<String, serde_json::Value>> {
+        let files: Vec<SourceFile> = file_paths
+            .iter()
+            .filter_map(|path| self.parse_go_file(path))
+            .collect();
+        self.extract_struct_examples(&files, base_dir.as_ref())
+    }
+
+    // Collect `example` tag values per struct, including models from imported packages
+    // that are referenced by body parameters and responses
+    fn extract_struct_examples(
+        &self,
+        files: &[SourceFile],
+        base_dir: &Path,
     ) -> HashMap<String, HashMap<String, serde_json::Value>> {
         let mut struct_examples: HashMap<String, HashMap<String, serde_json::Value>> =
             HashMap::new();
-        let imported_models_cache = Arc::new(Mutex::new(HashMap::<String, PathBuf>::new()));
-        
-        // Regular expressions for struct parsing
-        let struct_regex = Regex::new(r"type\s+(\w+)\s+struct\s*\{").unwrap();
-        let field_regex = Regex::new(r#"^\s*(\w+)\s+\w+\s+`[^`]*example:"([^"]*)"`"#).unwrap();
-        
-        for file_path in file_paths {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // Extract imports from this file
-                let mut imports = self.extract_imports(&content);
-                let _ = self.resolve_import_paths(&mut imports, base_dir.as_ref(), None);
-
-                // First pass: collect all local struct examples
-                let mut i = 0;
-                let lines: Vec<&str> = content.lines().collect();
-                
-                while i < lines.len() {
-                    if let Some(captures) = struct_regex.captures(lines[i]) {
-                        let struct_name = captures.get(1).unwrap().as_str();
-                        debug!("Found struct: {}", struct_name);
-                        
-                        let mut field_examples = HashMap::new();
-                        let mut j = i + 1;
-                        
-                        // Parse fields until we reach the closing brace
-                        while j < lines.len() && !lines[j].trim().starts_with("}") {
-                            if let Some(field_captures) = field_regex.captures(lines[j]) {
-                                let field_name = field_captures.get(1).unwrap().as_str();
-                                let example_value = field_captures.get(2).unwrap().as_str();
-                                
-                                debug!("  Field: {} with example: {}", field_name, example_value);
-                                
-                                // Try to parse as JSON
-                                let json_value = if let Ok(json) =
-                                    serde_json::from_str::<serde_json::Value>(example_value)
-                                {
-                                    json
-                                } else if let Ok(json) = serde_json::from_str::<serde_json::Value>(
-                                    &format!("\"{}\"", example_value),
-                                ) {
-                                    json
-                                } else {
-                                    serde_json::Value::String(example_value.to_string())
-                                };
-                                
-                                field_examples.insert(field_name.to_string(), json_value);
-                            }
-                            j += 1;
-                        }
-                        
-                        if !field_examples.is_empty() {
-                            struct_examples.insert(struct_name.to_string(), field_examples);
-                        }
-                        
-                        i = j;
+
+        // First pass: collect all local struct examples
+        for spec in files.iter().flat_map(|file| &file.ast.types) {
+            if let TypeExpr::Struct(fields) = &spec.ty {
+                debug!("Found struct: {}", spec.name);
+                let field_examples = self.struct_field_examples(fields);
+                if !field_examples.is_empty() {
+                    struct_examples.insert(spec.name.clone(), field_examples);
+                }
+            }
+        }
+
+        // Second pass: find models in body parameters and responses that refer to external files
+        for file in files {
+            let mut model_refs = HashSet::new();
+            for comment in &file.ast.comments {
+                for regex in [&PARAM_BODY_MODEL_REGEX, &RESPONSE_BODY_MODEL_REGEX] {
+                    for cap in regex.captures_iter(&comment.text) {
+                        model_refs.insert(cap[1].to_string());
                     }
-                    i += 1;
                 }
+            }
+            if model_refs.is_empty() {
+                continue;
+            }
+
+            let mut imports = self.import_infos(&file.ast);
+            let _ = self.resolve_import_paths(&mut imports, base_dir, None);
 
-                // Second pass: find models in body parameters and responses that refer to external files
-                let param_body_regex =
-                    Regex::new(r"@Param\s+\w+\s+body\s+([a-zA-Z0-9_.]+)").unwrap();
-                let response_body_regex =
-                    Regex::new(r"@(?:Success|Failure)\s+\d+\s+\{object\}\s+([a-zA-Z0-9_.]+)")
-                        .unwrap();
+            // Process each model reference to find it in import files
+            for model_ref in model_refs {
+                // Skip if we've already processed this model
+                if struct_examples.contains_key(&model_ref) {
+                    continue;
+                }
 
-                let mut model_refs = HashSet::new();
+                // Only qualified names (with package) can live in another package
+                let Some((package_alias, model_name)) = model_ref.split_once('.') else {
+                    continue;
+                };
+                let Some(import) = imports.iter().find(|import| import.alias == package_alias) else {
+                    continue;
+                };
+                let Some(fields) = self.find_imported_struct(model_name, import) else {
+                    continue;
+                };
 
-                // Collect all model references
-                for cap in param_body_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        model_refs.insert(m.as_str().to_string());
-                    }
+                let field_examples = self.struct_field_examples(&fields);
+                if !field_examples.is_empty() {
+                    // Store with the full qualified name
+                    struct_examples.insert(model_ref.clone(), field_examples.clone());
+                    // Also store with just the model name for backwards compatibility
+                    struct_examples.insert(model_name.to_string(), field_examples);
                 }
+            }
+        }
+
+        struct_examples
+    }
+
+    // Example values from the `example` tags of struct fields, keyed by JSON property name
+    fn struct_field_examples(&self, fields: &[go_ast::Field]) -> HashMap<String, serde_json::Value> {
+        let mut field_examples = HashMap::new();
+
+        for field in fields.iter().flat_map(StructField::from_ast) {
+            let (Some(field_name), Some(example_value)) = (field.json_name(), field.tag.get("example"))
+            else {
+                continue;
+            };
+            debug!("  Field: {} with example: {}", field_name, example_value);
+
+            // Try to parse as JSON
+            let json_value = if let Ok(json) = serde_json::from_str::<serde_json::Value>(example_value)
+            {
+                json
+            } else if let Ok(json) =
+                serde_json::from_str::<serde_json::Value>(&format!("\"{}\"", example_value))
+            {
+                json
+            } else {
+                serde_json::Value::String(example_value.to_string())
+            };
+
+            field_examples.insert(field_name, json_value);
+        }
+
+        field_examples
+    }
 
-                for cap in response_body_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        model_refs.insert(m.as_str().to_string());
+    // Map gin/go-playground validation rules (min, max, oneof, email, ...) onto schema constraints
+    fn apply_validation_rules(&self, rules: &[String], schema: &mut Schema) {
+        let mut rules = rules.iter();
+
+        while let Some(rule) = rules.next() {
+            if rule == "dive" {
+                // Remaining rules describe the elements of a slice or map
+                let mut item_rules: Vec<String> = rules.cloned().collect();
+
+                // `dive,keys,...,endkeys` validates the keys of a map before its values
+                if item_rules.first().is_some_and(|r| r == "keys") {
+                    let end = item_rules
+                        .iter()
+                        .position(|r| r == "endkeys")
+                        .unwrap_or(item_rules.len());
+                    let key_rules: Vec<String> = item_rules.drain(..end).skip(1).collect();
+                    if !item_rules.is_empty() {
+                        item_rules.remove(0);
+                    }
+                    if schema.additionalProperties.is_some() && !key_rules.is_empty() {
+                        let mut keys = Schema {
+                            type_: Some(serde_json::Value::String("string".to_string())),
+                            ..Default::default()
+                        };
+                        self.apply_validation_rules(&key_rules, &mut keys);
+                        schema.propertyNames = Some(Box::new(keys));
                     }
                 }
 
-                // Process each model reference to find it in import files
-                for model_ref in model_refs {
-                    // Skip if we've already processed this model
-                    if struct_examples.contains_key(&model_ref) {
-                        continue;
+                if let Some(items) = &mut schema.items {
+                    self.apply_validation_rules(&item_rules, items);
+                } else if let Some(value @ serde_json::Value::Object(_)) =
+                    &mut schema.additionalProperties
+                {
+                    if let Ok(mut values) = serde_json::from_value::<Schema>(value.clone()) {
+                        self.apply_validation_rules(&item_rules, &mut values);
+                        if let Ok(updated) = serde_json::to_value(values) {
+                            *value = updated;
+                        }
                     }
+                }
+                return;
+            }
 
-                    // Check if it's a qualified name (with package)
-                    let parts: Vec<&str> = model_ref.split('.').collect();
-                    if parts.len() >= 2 {
-                        let package_alias = parts[0];
-                        let model_name = parts[1];
-
-                        // Find the import that matches this package
-                        for import in &imports {
-                            if import.alias == package_alias {
-                                // Try to find the model in the imported package
-                                let mut visited = HashSet::new();
-                                if let Some((model_file, model_content)) = self.find_model_file(
-                                    model_name,
-                                    &imports,
-                                    &content,
-                                    &mut visited,
-                                ) {
-                                    // Parse the model file to extract examples
-                                    let model_lines: Vec<&str> = model_content.lines().collect();
-                                    let model_struct_pattern =
-                                        format!(r"type\s+{}\s+struct\s*\{{", model_name);
-                                    let model_struct_regex =
-                                        Regex::new(&model_struct_pattern).unwrap();
-
-                                    let mut i = 0;
-                                    while i < model_lines.len() {
-                                        if model_struct_regex.captures(model_lines[i]).is_some()
-                                        {
-                                            let mut field_examples = HashMap::new();
-                                            let mut j = i + 1;
-
-                                            // Parse fields until we reach the closing brace
-                                            while j < model_lines.len()
-                                                && !model_lines[j].trim().starts_with("}")
-                                            {
-                                                if let Some(field_captures) =
-                                                    field_regex.captures(model_lines[j])
-                                                {
-                                                    let field_name =
-                                                        field_captures.get(1).unwrap().as_str();
-                                                    let example_value =
-                                                        field_captures.get(2).unwrap().as_str();
-
-                                                    debug!("  Field in imported model: {} with example: {}", field_name, example_value);
-
-                                                    // Try to parse as JSON
-                                                    let json_value = if let Ok(json) =
-                                                        serde_json::from_str::<serde_json::Value>(
-                                                            example_value,
-                                                        ) {
-                                                        json
-                                                    } else if let Ok(json) =
-                                                        serde_json::from_str::<serde_json::Value>(
-                                                            &format!("\"{}\"", example_value),
-                                                        )
-                                                    {
-                                                        json
-                                                    } else {
-                                                        serde_json::Value::String(
-                                                            example_value.to_string(),
-                                                        )
-                                                    };
-
-                                                    field_examples
-                                                        .insert(field_name.to_string(), json_value);
-                                                }
-                                                j += 1;
-                                            }
+            // Alternatives such as `rgb|rgba` cannot be expressed as a single constraint
+            if rule.contains('|') {
+                continue;
+            }
 
-                                            if !field_examples.is_empty() {
-                                                // Store with the full qualified name
-                                                struct_examples.insert(
-                                                    model_ref.clone(),
-                                                    field_examples.clone(),
-                                                );
-                                                // Also store with just the model name for backwards compatibility
-                                                struct_examples
-                                                    .insert(model_name.to_string(), field_examples);
-                                            }
+            let (name, value) = match rule.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (rule.as_str(), None),
+            };
+            // The type behind a reference is unknown here, so a bound could be a
+            // length, an item count or a value
+            if schema.ref_.is_some()
+                && matches!(name, "min" | "max" | "gte" | "lte" | "gt" | "lt" | "len")
+            {
+                continue;
+            }
 
-                                            break;
-                                        }
-                                        i += 1;
-                                    }
+            let kind = schema.type_.as_ref().and_then(|t| t.as_str()).unwrap_or("");
+            let text = value.unwrap_or("");
+            let number = text.parse::<f64>().ok();
 
-                                    // Cache this model file for future reference
-                                    imported_models_cache
-                                        .lock()
-                                        .unwrap()
-                                        .insert(model_ref.clone(), model_file);
-                                    break;
-                                }
-                            }
-                        }
+            match (name, number) {
+                ("min" | "gte", Some(n)) => match kind {
+                    "string" => schema.minLength = Some(n as u64),
+                    "array" => schema.minItems = Some(n as u64),
+                    "object" => schema.minProperties = Some(n as u64),
+                    _ => schema.minimum = Some(n),
+                },
+                ("max" | "lte", Some(n)) => match kind {
+                    "string" => schema.maxLength = Some(n as u64),
+                    "array" => schema.maxItems = Some(n as u64),
+                    "object" => schema.maxProperties = Some(n as u64),
+                    _ => schema.maximum = Some(n),
+                },
+                ("gt", Some(n)) => match kind {
+                    "string" => schema.minLength = Some(n as u64 + 1),
+                    "array" => schema.minItems = Some(n as u64 + 1),
+                    "object" => schema.minProperties = Some(n as u64 + 1),
+                    _ => schema.exclusiveMinimum = Some(n.into()),
+                },
+                ("lt", Some(n)) => match kind {
+                    "string" => schema.maxLength = Some((n as u64).saturating_sub(1)),
+                    "array" => schema.maxItems = Some((n as u64).saturating_sub(1)),
+                    "object" => schema.maxProperties = Some((n as u64).saturating_sub(1)),
+                    _ => schema.exclusiveMaximum = Some(n.into()),
+                },
+                ("len", Some(n)) => match kind {
+                    "string" => {
+                        schema.minLength = Some(n as u64);
+                        schema.maxLength = Some(n as u64);
+                    }
+                    "array" => {
+                        schema.minItems = Some(n as u64);
+                        schema.maxItems = Some(n as u64);
                     }
+                    _ => {}
+                },
+                ("oneof", _) => {
+                    let values = text
+                        .split_whitespace()
+                        .map(|v| v.trim_matches('\''))
+                        .map(|v| match kind {
+                            "integer" => v
+                                .parse::<i64>()
+                                .map(serde_json::Value::from)
+                                .unwrap_or_else(|_| serde_json::Value::String(v.to_string())),
+                            "number" => v
+                                .parse::<f64>()
+                                .map(serde_json::Value::from)
+                                .unwrap_or_else(|_| serde_json::Value::String(v.to_string())),
+                            _ => serde_json::Value::String(v.to_string()),
+                        })
+                        .collect();
+                    schema.enum_values = Some(values);
+                }
+                ("unique", _) if kind == "array" => schema.uniqueItems = Some(true),
+                ("email", _) => schema.format = Some("email".to_string()),
+                ("url" | "uri" | "http_url", _) => schema.format = Some("uri".to_string()),
+                ("uuid" | "uuid3" | "uuid4" | "uuid5" | "uuid_rfc4122", _) => {
+                    schema.format = Some("uuid".to_string())
+                }
+                ("ipv4" | "ip4_addr", _) => schema.format = Some("ipv4".to_string()),
+                ("ipv6" | "ip6_addr", _) => schema.format = Some("ipv6".to_string()),
+                ("hostname" | "hostname_rfc1123", _) => {
+                    schema.format = Some("hostname".to_string())
                 }
+                ("datetime", _) => schema.format = Some("date-time".to_string()),
+                ("alpha", _) => schema.pattern = Some("^[a-zA-Z]+$".to_string()),
+                ("alphanum", _) => schema.pattern = Some("^[a-zA-Z0-9]+$".to_string()),
+                ("numeric", _) => {
+                    schema.pattern = Some("^[-+]?[0-9]+(?:\\.[0-9]+)?$".to_string())
+                }
+                ("number", _) => schema.pattern = Some("^[0-9]+$".to_string()),
+                ("hexadecimal", _) => {
+                    schema.pattern = Some("^(0[xX])?[0-9a-fA-F]+$".to_string())
+                }
+                ("lowercase", _) => schema.pattern = Some("^[^A-Z]*$".to_string()),
+                ("uppercase", _) => schema.pattern = Some("^[^a-z]*$".to_string()),
+                ("e164", _) => schema.pattern = Some("^\\+[1-9]?[0-9]{7,14}$".to_string()),
+                ("startswith", _) if !text.is_empty() => {
+                    schema.pattern = Some(format!("^{}", regex::escape(text)))
+                }
+                ("endswith", _) if !text.is_empty() => {
+                    schema.pattern = Some(format!("{}$", regex::escape(text)))
+                }
+                ("contains", _) if !text.is_empty() => schema.pattern = Some(regex::escape(text)),
+                _ => {}
             }
         }
-        
-        struct_examples
     }
 
     // Add back the normalize_mime_type method that was removed
@@ -2190,7 +3633,7 @@ impl GoParser {
             "xml" => "application/xml".to_string(),
             "plain" | "text" => "text/plain".to_string(),
             "html" => "text/html".to_string(),
-            "form" | "form-data" | "multipart" => "multipart/form-data".to_string(),
+            "form" | "form-data" | "multipart" | "mpfd" => "multipart/form-data".to_string(),
             "form-urlencoded" | "urlencoded" => "application/x-www-form-urlencoded".to_string(),
             "octet-stream" | "binary" => "application/octet-stream".to_string(),
             mime => {
@@ -2203,7 +3646,8 @@ impl GoParser {
         }
     }
 
-    fn parse_parameter(&self, param_str: &str) -> Result<Parameter, ParserError> {
+    fn parse_parameter(&self, annotation: &Annotation) -> Result<Parameter, ParserError> {
+        let param_str = annotation.value.as_str();
         // Format: name [paramType] [dataType] [required] [description] [attributes...]
         debug!("Parsing parameter: {}", param_str);
 
@@ -2332,66 +3776,35 @@ impl GoParser {
             }
         } else {
             // Direct model reference (without object keyword)
-            if data_type.contains(".") {
+            if data_type.contains('{') {
+                parameter.schema = Some(self.parse_type_expression(&data_type));
+            } else if let Some(schema) = self.well_known_type_schema(&data_type) {
+                parameter.schema = Some(schema);
+            } else if data_type.contains(".") || data_type.starts_with(char::is_uppercase) {
+                // Qualified names and exported types of the same package are models
                 parameter.schema = Some(Schema {
                     ref_: Some(format!("#/components/schemas/{}", data_type)),
                     ..Default::default()
                 });
                 } else {
-                    // Primitive type
-                    parameter.schema = Some(Schema {
-                        type_: Some(serde_json::Value::String(data_type.to_string())),
-                        ..Default::default()
-                    });
-                    
-                    // Handle array types
-                    if data_type.starts_with("[]") {
-                        parameter.schema = Some(Schema {
-                            type_: Some(serde_json::Value::String("array".to_string())),
-                            items: Some(Box::new(Schema {
-                                type_: Some(serde_json::Value::String(data_type[2..].to_string())),
-                                ..Default::default()
-                            })),
-                            ..Default::default()
-                        });
+                    // Primitive type, or an array of them such as []int
+                    parameter.schema = Some(self.param_type_schema(&data_type));
                 }
-                    }
                 }
                 
                 // Parse optional attributes
         if parts.len() > 5 {
             let attrs_str = parts[5..].join(" ");
-            for attr in attrs_str.split_whitespace() {
-                        if attr.starts_with("Format(") && attr.ends_with(")") {
-                            let format = &attr[7..attr.len() - 1];
-                            if let Some(ref mut schema) = parameter.schema {
-                                schema.format = Some(format.to_string());
-                            }
-                        } else if attr.starts_with("Enums(") && attr.ends_with(")") {
-                            let enums = &attr[6..attr.len() - 1];
-                    let enum_values: Vec<serde_json::Value> = enums
-                        .split(',')
-                                .map(|s| serde_json::Value::String(s.trim().to_string()))
-                                .collect();
-                            if let Some(ref mut schema) = parameter.schema {
-                                schema.enum_values = Some(enum_values);
-                            }
-                        } else if attr.starts_with("Default(") && attr.ends_with(")") {
-                            let default = &attr[8..attr.len() - 1];
-                            if let Some(ref mut schema) = parameter.schema {
-                                schema.default = Some(serde_json::Value::String(default.to_string()));
-                            }
-                        } else if attr.starts_with("Example(") && attr.ends_with(")") {
-                            let example = &attr[8..attr.len() - 1];
-                            // Try to parse as JSON first
-                            if let Ok(json_value) = serde_json::from_str(example) {
-                                parameter.example = Some(json_value);
-                            } else {
-                                parameter.example = Some(serde_json::Value::String(example.to_string()));
-                            }
-                        }
-                    }
-                }
+            for captures in PARAM_ATTRIBUTE_REGEX.captures_iter(&attrs_str) {
+                let value = captures[2].trim();
+                self.apply_param_attribute(
+                    &mut parameter,
+                    &captures[1],
+                    value,
+                    annotation.location.as_ref(),
+                )?;
+            }
+        }
                 
                 // Check for inline example
                 if param_str.contains("{example=") {
@@ -2419,6 +3832,121 @@ impl GoParser {
                 Ok(parameter)
     }
 
+    // Schema for a swag parameter data type (`integer`, `bool`, `[]int`, ...);
+    // Go type names are accepted for the primitive types
+    fn param_type_schema(&self, data_type: &str) -> Schema {
+        if let Some(item_type) = data_type.strip_prefix("[]") {
+            return Schema {
+                type_: Some(serde_json::Value::String("array".to_string())),
+                items: Some(Box::new(self.param_type_schema(item_type))),
+                ..Default::default()
+            };
+        }
+
+        let (type_, format) = match data_type {
+            "integer" | "int" | "int8" | "int16" | "uint" | "uint8" | "uint16" | "uint32"
+            | "uint64" | "byte" | "rune" => ("integer", None),
+            "int32" => ("integer", Some("int32")),
+            "int64" => ("integer", Some("int64")),
+            "number" => ("number", None),
+            "float32" | "float" => ("number", Some("float")),
+            "float64" | "double" => ("number", Some("double")),
+            "boolean" | "bool" => ("boolean", None),
+            other => (other, None),
+        };
+        Schema {
+            type_: Some(serde_json::Value::String(type_.to_string())),
+            format: format.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    // Apply one `attribute(value)` of a @Param line, e.g. `minimum(1)` or `collectionFormat(multi)`;
+    // attribute names are case-insensitive, as in swag
+    fn apply_param_attribute(
+        &self,
+        parameter: &mut Parameter,
+        attribute: &str,
+        value: &str,
+        location: Option<&SourceLocation>,
+    ) -> Result<(), ParserError> {
+        let schema = parameter.schema.get_or_insert_with(Schema::default);
+        // Enums and defaults of array parameters describe their items
+        let is_array = schema.type_.as_ref().and_then(|t| t.as_str()) == Some("array");
+        let number = |value: &str| {
+            value.parse::<f64>().map_err(|_| {
+                ParserError::ParameterParseError(format!(
+                    "{}({}) of parameter {} is not a number",
+                    attribute, value, parameter.name
+                ))
+            })
+        };
+        let length = |value: &str| {
+            value.parse::<u64>().map_err(|_| {
+                ParserError::ParameterParseError(format!(
+                    "{}({}) of parameter {} is not a length",
+                    attribute, value, parameter.name
+                ))
+            })
+        };
+
+        match attribute.to_lowercase().as_str() {
+            "format" => schema.format = Some(value.to_string()),
+            "enums" => {
+                let target = match &mut schema.items {
+                    Some(items) if is_array => items.as_mut(),
+                    _ => &mut *schema,
+                };
+                let values = value
+                    .split(',')
+                    .map(|v| coerce_param_value(v.trim(), target))
+                    .collect();
+                target.enum_values = Some(values);
+            }
+            "default" => schema.default = Some(coerce_param_value(value, schema)),
+            "example" => parameter.example = Some(coerce_param_value(value, schema)),
+            "minimum" => schema.minimum = Some(number(value)?),
+            "maximum" => schema.maximum = Some(number(value)?),
+            "multipleof" => schema.multipleOf = Some(number(value)?),
+            "minlength" => schema.minLength = Some(length(value)?),
+            "maxlength" => schema.maxLength = Some(length(value)?),
+            "collectionformat" => {
+                // Swagger 2 collection formats map onto OpenAPI 3 styles
+                let (style, explode) = match value {
+                    "csv" if matches!(parameter.in_type.as_str(), "path" | "header") => {
+                        ("simple", false)
+                    }
+                    "csv" => ("form", false),
+                    "multi" => ("form", true),
+                    "ssv" => ("spaceDelimited", false),
+                    "pipes" => ("pipeDelimited", false),
+                    _ => {
+                        self.report(
+                            ParserError::ParameterParseError(format!(
+                                "Collection format {} of parameter {} has no OpenAPI 3 equivalent",
+                                value, parameter.name
+                            ))
+                            .at(location),
+                        );
+                        return Ok(());
+                    }
+                };
+                parameter.style = Some(style.to_string());
+                parameter.explode = Some(explode);
+            }
+            // Same syntax as the `extensions` struct tag
+            "extensions" => schema.extensions.extend(parse_extension_list(value)),
+            _ => self.report(
+                ParserError::UnknownAttribute(format!(
+                    "{}({}) on parameter {}",
+                    attribute, value, parameter.name
+                ))
+                .at(location),
+            ),
+        }
+        Ok(())
+    }
+
     fn parse_response(&self, response_str: &str) -> Result<Response, ParserError> {
         // Format: code description [model] [example]
         debug!("Parsing response: {}", response_str);
@@ -2439,8 +3967,8 @@ impl GoParser {
             (normalized_resp.as_str(), None)
         };
 
-        // Split the response into parts
-        let parts: Vec<&str> = response_part.split_whitespace().collect();
+        // Split the response into parts, keeping composed models like `Wrapper{data=[]User, meta=Meta}` together
+        let parts = self.split_outside_brackets(response_part, char::is_whitespace);
 
         // We need at least a status code
         if parts.is_empty() {
@@ -2452,6 +3980,12 @@ impl GoParser {
 
         // Parse the status code
         let code = parts[0].trim();
+        if !code.split(',').all(|c| STATUS_CODE_REGEX.is_match(c)) {
+            return Err(ParserError::ResponseParseError(format!(
+                "Invalid status code {}: expected an HTTP status code, a range such as 4XX, or default",
+                code
+            )));
+        }
                 let code = if code == "default" {
                     "default".to_string()
                 } else {
@@ -2518,10 +4052,7 @@ impl GoParser {
                         response.content.insert(
                             "application/json".to_string(),
                             MediaType {
-                                schema: Some(Schema {
-                                    ref_: Some(format!("#/components/schemas/{}", model_name)),
-                                    ..Default::default()
-                                }),
+                                schema: Some(self.parse_type_expression(model_name)),
                                 ..Default::default()
                             },
                         );
@@ -2532,13 +4063,10 @@ impl GoParser {
                             MediaType {
                                 schema: Some(Schema {
                                     type_: Some(serde_json::Value::String("array".to_string())),
-                                    items: Some(Box::new(Schema {
-                                ref_: Some(format!("#/components/schemas/{}", model_name)),
+                                    items: Some(Box::new(self.parse_type_expression(model_name))),
+                                    ..Default::default()
+                                }),
                                 ..Default::default()
-                                    })),
-                            ..Default::default()
-                                }),
-                                    ..Default::default()
                             },
                         );
                     }
@@ -2575,8 +4103,10 @@ impl GoParser {
                         if let Some(media_type) = response.content.get_mut("application/json") {
                             media_type.example = Some(example_value);
                         } else {
-                            let mut media_type = MediaType::default();
-                            media_type.example = Some(example_value);
+                            let media_type = MediaType {
+                                example: Some(example_value),
+                                ..Default::default()
+                            };
                     response
                         .content
                         .insert("application/json".to_string(), media_type);
@@ -2596,354 +4126,140 @@ impl GoParser {
                 Ok(response)
     }
     
-    // Extract schema definitions from Go structs in the codebase
-    #[allow(dead_code)]
-    pub fn extract_struct_schemas(&self, file_paths: &[PathBuf]) -> HashMap<String, Schema> {
-        use regex::Regex;
-        let mut schemas: HashMap<String, Schema> = HashMap::new();
-        
-        // Regular expressions for struct parsing
-        let struct_regex = Regex::new(r"type\s+(\w+)\s+struct\s*\{").unwrap();
-        let field_regex = Regex::new(r#"^\s*(\w+)\s+(\w+(?:\[\])?\*?)(?:\s+`[^`]*`)?.*$"#).unwrap();
-        
-        // Track package names from imports to handle qualified model names
-        let mut package_imports: HashMap<String, String> = HashMap::new();
-        let import_regex = Regex::new(r#"import\s+\(\s*((?:[^()]*\n)*)\s*\)"#).unwrap();
-        let import_line_regex = Regex::new(r#"\s*(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
-        let single_import_regex =
-            Regex::new(r#"import\s+(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
+    // Parse `200,201 {string} X-Request-ID "trace id"` into the response codes it
+    // applies to, the header name and the header
+    fn parse_header(&self, header_str: &str) -> Result<(Vec<String>, String, Header), ParserError> {
+        let captures = HEADER_REGEX.captures(header_str.trim()).ok_or_else(|| {
+            ParserError::HeaderParseError(format!(
+                "expected `<codes> {{type}} <name> [description]`, got: {}",
+                header_str
+            ))
+        })?;
+
+        let codes = captures[1]
+            .split(',')
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(str::to_string)
+            .collect();
+        let description = captures
+            .get(4)
+            .map(|m| m.as_str().trim().trim_matches('"').to_string())
+            .filter(|description| !description.is_empty());
+
+        let header = Header {
+            description,
+            schema: Some(self.parse_type_expression(&captures[2])),
+            ..Default::default()
+        };
+        Ok((codes, captures[3].to_string(), header))
+    }
 
-        for file_path in file_paths {
-            debug!("Extracting schemas from file: {:?}", file_path);
-
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // Extract package imports first
-                // Multi-line imports
-                if let Some(caps) = import_regex.captures(&content) {
-                    if let Some(import_block) = caps.get(1) {
-                        for line in import_block.as_str().lines() {
-                            if let Some(m) = import_line_regex.captures(line) {
-                                let alias = m.get(1).map_or_else(
-                                    || {
-                                        // If no alias, use the last part of the path
-                                        let path = m.get(2).unwrap().as_str();
-                                        path.split('/').next_back().unwrap_or(path).to_string()
-                                    },
-                                    |a| a.as_str().to_string(),
-                                );
-                                let path = m.get(2).unwrap().as_str().to_string();
-                                package_imports.insert(alias, path);
-                            }
-                        }
-                    }
-                }
+    // Build the schema for a type such as `[]models.User` or swag's composition syntax
+    // `response.Response{data=[]models.User,meta=pagination.Meta}`, which overrides
+    // properties of the wrapper through an allOf
+    fn parse_type_expression(&self, type_expr: &str) -> Schema {
+        let type_expr = type_expr.trim();
 
-                // Single-line imports
-                for caps in single_import_regex.captures_iter(&content) {
-                    let alias = caps.get(1).map_or_else(
-                        || {
-                            // If no alias, use the last part of the path
-                            let path = caps.get(2).unwrap().as_str();
-                            path.split('/').next_back().unwrap_or(path).to_string()
-                        },
-                        |a| a.as_str().to_string(),
+        if let Some(item_expr) = type_expr.strip_prefix("[]") {
+            return Schema {
+                type_: Some(serde_json::Value::String("array".to_string())),
+                items: Some(Box::new(self.parse_type_expression(item_expr))),
+                ..Default::default()
+            };
+        }
+
+        if let (Some(open), true) = (type_expr.find('{'), type_expr.ends_with('}')) {
+            let wrapper = self.parse_type_expression(&type_expr[..open]);
+            let mut overrides = Schema {
+                type_: Some(serde_json::Value::String("object".to_string())),
+                ..Default::default()
+            };
+
+            let fields = &type_expr[open + 1..type_expr.len() - 1];
+            for field in self.split_outside_brackets(fields, |c| c == ',') {
+                if let Some((name, field_expr)) = field.split_once('=') {
+                    overrides.properties.insert(
+                        name.trim().to_string(),
+                        Box::new(self.parse_type_expression(field_expr)),
                     );
-                    let path = caps.get(2).unwrap().as_str().to_string();
-                    package_imports.insert(alias, path);
+                } else {
+                    warn!("Ignoring invalid field override '{}' in {}", field, type_expr);
                 }
+            }
 
-                // Now parse structs
-                let lines: Vec<&str> = content.lines().collect();
-                let mut i = 0;
-                
-                while i < lines.len() {
-                    if let Some(captures) = struct_regex.captures(lines[i]) {
-                        let struct_name = captures.get(1).unwrap().as_str();
-                        debug!("Found struct for schema: {}", struct_name);
-                        
-                        let mut schema = Schema {
-                            type_: Some(serde_json::Value::String("object".to_string())),
-                            properties: HashMap::new(),
-                            ..Default::default()
-                        };
-                        
-                        let mut required_fields = Vec::new();
-                        let mut j = i + 1;
-                        
-                        // Parse fields until we reach the closing brace
-                        while j < lines.len() && !lines[j].trim().starts_with("}") {
-                            if let Some(field_captures) = field_regex.captures(lines[j]) {
-                                let field_name = field_captures.get(1).unwrap().as_str();
-                                let field_type = field_captures.get(2).unwrap().as_str();
+            return Schema {
+                allOf: Some(vec![wrapper, overrides]),
+                ..Default::default()
+            };
+        }
 
-                                debug!("  Field: {} with type: {}", field_name, field_type);
-                                
-                                // Convert Go types to OpenAPI types
-                                let field_schema = match field_type {
-                                    "string" => Schema {
-                                        type_: Some(serde_json::Value::String(
-                                            "string".to_string(),
-                                        )),
-                                        ..Default::default()
-                                    },
-                                    "int" | "int8" | "int16" | "int32" | "int64" | "uint"
-                                    | "uint8" | "uint16" | "uint32" | "uint64" => Schema {
-                                        type_: Some(serde_json::Value::String(
-                                            "integer".to_string(),
-                                        )),
-                                        ..Default::default()
-                                    },
-                                    "float32" | "float64" => Schema {
-                                        type_: Some(serde_json::Value::String(
-                                            "number".to_string(),
-                                        )),
-                                        ..Default::default()
-                                    },
-                                    "bool" => Schema {
-                                        type_: Some(serde_json::Value::String(
-                                            "boolean".to_string(),
-                                        )),
-                                        ..Default::default()
-                                    },
-                                    t if t.starts_with("[]") => {
-                                        // Array type
-                                        let item_type = &t[2..]; // Remove "[]" prefix
-
-                                        // Handle qualified references in arrays, e.g. []packageName.Type
-                                        if item_type.contains('.') {
-                                            let parts: Vec<&str> = item_type.split('.').collect();
-                                            if parts.len() == 2 {
-                                                let package_alias = parts[0];
-                                                let type_name = parts[1];
-
-                                                // Full qualified name for the reference
-                                                let ref_name =
-                                                    format!("{}.{}", package_alias, type_name);
-
-                                                Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "array".to_string(),
-                                                    )),
-                                                    items: Some(Box::new(Schema {
-                                                        ref_: Some(format!(
-                                                            "#/components/schemas/{}",
-                                                            ref_name
-                                                        )),
-                                                        ..Default::default()
-                                                    })),
-                                                    ..Default::default()
-                                                }
-                                            } else {
-                                                Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "array".to_string(),
-                                                    )),
-                                                    items: Some(Box::new(Schema {
-                                                        ref_: Some(format!(
-                                                            "#/components/schemas/{}",
-                                                            item_type
-                                                        )),
-                                                        ..Default::default()
-                                                    })),
-                                                    ..Default::default()
-                                                }
-                                            }
-                                        } else {
-                                        let item_schema = match item_type {
-                                            "string" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "string".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                                "int" | "int8" | "int16" | "int32" | "int64"
-                                                | "uint" | "uint8" | "uint16" | "uint32"
-                                                | "uint64" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "integer".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            "float32" | "float64" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "number".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            "bool" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "boolean".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            _ => {
-                                                // Reference to another type
-                                                Schema {
-                                                        ref_: Some(format!(
-                                                            "#/components/schemas/{}",
-                                                            item_type
-                                                        )),
-                                                    ..Default::default()
-                                                }
-                                            }
-                                        };
-                                        
-                                        Schema {
-                                                type_: Some(serde_json::Value::String(
-                                                    "array".to_string(),
-                                                )),
-                                            items: Some(Box::new(item_schema)),
-                                            ..Default::default()
-                                        }
-                                        }
-                                    }
-                                    t if t.starts_with("*") => {
-                                        // Pointer type (optional)
-                                        let base_type = &t[1..]; // Remove "*" prefix
-
-                                        // Handle qualified references in pointers, e.g. *packageName.Type
-                                        if base_type.contains('.') {
-                                            let parts: Vec<&str> = base_type.split('.').collect();
-                                            if parts.len() == 2 {
-                                                let package_alias = parts[0];
-                                                let type_name = parts[1];
-
-                                                // Full qualified name for the reference
-                                                let ref_name =
-                                                    format!("{}.{}", package_alias, type_name);
-
-                                                Schema {
-                                                    ref_: Some(format!(
-                                                        "#/components/schemas/{}",
-                                                        ref_name
-                                                    )),
-                                                    ..Default::default()
-                                                }
-                                            } else {
-                                                Schema {
-                                                    ref_: Some(format!(
-                                                        "#/components/schemas/{}",
-                                                        base_type
-                                                    )),
-                                                    ..Default::default()
-                                                }
-                                            }
-                                        } else {
-                                        match base_type {
-                                            "string" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "string".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                                "int" | "int8" | "int16" | "int32" | "int64"
-                                                | "uint" | "uint8" | "uint16" | "uint32"
-                                                | "uint64" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "integer".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            "float32" | "float64" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "number".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            "bool" => Schema {
-                                                    type_: Some(serde_json::Value::String(
-                                                        "boolean".to_string(),
-                                                    )),
-                                                ..Default::default()
-                                            },
-                                            _ => {
-                                                // Reference to another type
-                                                Schema {
-                                                        ref_: Some(format!(
-                                                            "#/components/schemas/{}",
-                                                            base_type
-                                                        )),
-                                                    ..Default::default()
-                                                }
-                                            }
-                                        }
-                                        }
-                                    }
-                                    t if t.contains('.') => {
-                                        // This is a reference to a type in another package (e.g., user.User)
-                                        let parts: Vec<&str> = t.split('.').collect();
-                                        if parts.len() == 2 {
-                                            let package_alias = parts[0];
-                                            let type_name = parts[1];
-
-                                            // Full qualified name for the reference
-                                            let ref_name =
-                                                format!("{}.{}", package_alias, type_name);
-
-                                            Schema {
-                                                ref_: Some(format!(
-                                                    "#/components/schemas/{}",
-                                                    ref_name
-                                                )),
-                                                ..Default::default()
-                                            }
-                                        } else {
-                                            Schema {
-                                                ref_: Some(format!("#/components/schemas/{}", t)),
-                                                ..Default::default()
-                                            }
-                                        }
-                                    }
-                                    _ => {
-                                        // Reference to another type
-                                        Schema {
-                                            ref_: Some(format!(
-                                                "#/components/schemas/{}",
-                                                field_type
-                                            )),
-                                            ..Default::default()
-                                        }
-                                    }
-                                };
-                                
-                                // Check if the field is required (not a pointer type)
-                                if !field_type.starts_with("*") {
-                                    required_fields.push(field_name.to_string());
-                                }
-                                
-                                schema
-                                    .properties
-                                    .insert(field_name.to_string(), Box::new(field_schema));
-                            }
-                            j += 1;
-                        }
-                        
-                        // Add required fields if any
-                        if !required_fields.is_empty() {
-                            schema.required = Some(required_fields);
-                        }
-                        
-                        // Add the schema with different names for better reference resolution
-
-                        // 1. Add with the simple name (e.g., "User")
-                        schemas.insert(struct_name.to_string(), schema.clone());
-
-                        // 2. Also add package-qualified names for all known package imports
-                        // This handles references like "userModel.User" by creating schemas
-                        // with both names "User" and "userModel.User"
-                        for package_alias in package_imports.keys() {
-                            let qualified_name = format!("{}.{}", package_alias, struct_name);
-                            debug!("Adding schema with qualified name: {}", qualified_name);
-                            schemas.insert(qualified_name, schema.clone());
-                        }
+        match type_expr {
+            "integer" | "number" | "boolean" | "object" => Schema {
+                type_: Some(serde_json::Value::String(type_expr.to_string())),
+                ..Default::default()
+            },
+            _ => self.convert_go_type_to_schema(type_expr),
+        }
+    }
+
+    // Split on separators that are not nested inside `{...}` or `[...]`, dropping empty pieces
+    fn split_outside_brackets<'a>(
+        &self,
+        input: &'a str,
+        is_separator: impl Fn(char) -> bool,
+    ) -> Vec<&'a str> {
+        let mut pieces = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (index, c) in input.char_indices() {
+            match c {
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth = depth.saturating_sub(1),
+                c if depth == 0 && is_separator(c) => {
+                    pieces.push(&input[start..index]);
+                    start = index + c.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        pieces.push(&input[start..]);
+
+        pieces
+            .into_iter()
+            .map(str::trim)
+            .filter(|piece| !piece.is_empty())
+            .collect()
+    }
+
+    // Extract schema definitions from Go structs in the codebase
+    #[allow(dead_code)]
+    pub fn extract_struct_schemas(&self, file_paths: &[PathBuf]) -> HashMap<String, Schema> {
+        let mut schemas: HashMap<String, Schema> = HashMap::new();
+        let mut embedded_fields: HashMap<String, Vec<StructField>> = HashMap::new();
 
-                        i = j;
+        for file_path in file_paths {
+            let Some(file) = self.parse_go_file(file_path) else {
+                continue;
+            };
+
+            // Generic structs only have schemas per instantiation
+            for spec in file.ast.types.iter().filter(|spec| spec.type_params.is_empty()) {
+                if let TypeExpr::Struct(fields) = &spec.ty {
+                    debug!("Found struct: {}", spec.name);
+                    let (mut schema, _, promoted_fields) =
+                        self.build_struct_schema(fields, &HashMap::new());
+                    self.apply_doc_comment(spec.doc.as_ref(), &mut schema);
+                    if !promoted_fields.is_empty() {
+                        embedded_fields.insert(spec.name.clone(), promoted_fields);
                     }
-                    i += 1;
+                    schemas.insert(spec.name.clone(), schema);
                 }
             }
         }
-        
+
+        self.resolve_embedded_structs(&mut schemas, &embedded_fields);
         schemas
     }
 
@@ -3028,280 +4344,635 @@ impl GoParser {
         }
     }
 
-    // New method to collect model names that are referenced in annotations
-    #[allow(dead_code)]
-    fn collect_referenced_models(&self, file_paths: &[PathBuf]) -> HashSet<String> {
-        let mut referenced_models = HashSet::new();
+    // Extract schemas for the referenced models and, transitively, the types they use
+    fn extract_referenced_schemas(
+        &self,
+        files: &[SourceFile],
+        referenced_models: &HashSet<String>,
+    ) -> HashMap<String, Schema> {
+        let mut schemas: HashMap<String, Schema> = HashMap::new();
+        let mut embedded_fields: HashMap<String, Vec<StructField>> = HashMap::new();
 
-        // Patterns to match model references in annotations
-        let param_regex = Regex::new(r"@Param\s+\w+\s+body\s+([a-zA-Z0-9_.]+)").unwrap();
-        let request_body_regex =
-            Regex::new(r"@RequestBody\s+.*\{object\}\s+([a-zA-Z0-9_.]+)").unwrap();
-        // Make the response regex more flexible to handle various whitespace patterns
-        let response_regex = Regex::new(
-            r"@(?:Success|Failure|Response)\s+\d+\s+\{(?:object|array)\}\s*([a-zA-Z0-9_.]+)",
-        )
-        .unwrap();
+        // Index type declarations by name, remembering the package declaring them
+        let type_specs = index_type_specs(files);
+        // Track package names from imports to handle qualified model names
+        let package_aliases: HashSet<&str> = files
+            .iter()
+            .flat_map(|file| file.ast.imports.iter().map(ImportSpec::package_name))
+            .collect();
 
-        for file_path in file_paths {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // Find all model references in @Param annotations
-                for cap in param_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
-
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
-                    }
-                }
+        // Add some common response types that might be referenced
+        // These are basic schema definitions for commonly referenced types
+        self.add_common_schemas(&mut schemas);
 
-                // Find all model references in @RequestBody annotations
-                for cap in request_body_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
+        // Constants of named types become enum values of those types
+        let enum_values = self.collect_enum_values(files);
 
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
-                    }
-                }
+        let mut models_to_process: Vec<String> = referenced_models.iter().cloned().collect();
+        models_to_process.sort();
+        let mut processed_models = HashSet::new();
+        let mut attempted_models = HashSet::new();
+        // Where each schema built from a declaration was declared
+        let mut declared_at: Vec<(String, SourceLocation)> = Vec::new();
 
-                // Find all model references in @Success, @Failure, and @Response annotations
-                for cap in response_regex.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let model_name = m.as_str().to_string();
-                        referenced_models.insert(model_name.clone());
+        while let Some(model_name) = models_to_process.pop() {
+            // Never retry models that were not found
+            if !attempted_models.insert(model_name.clone()) {
+                continue;
+            }
 
-                        // Also add the bare model name without package prefix
-                        if model_name.contains('.') {
-                            if let Some(base_name) = model_name.split('.').next_back() {
-                                referenced_models.insert(base_name.to_string());
-                            }
-                        }
+            let (type_name, type_args) = self
+                .split_generic_instantiation(&model_name)
+                .unwrap_or((model_name.as_str(), Vec::new()));
+            let (package, simple_name) = match type_name.rsplit_once('.') {
+                Some((package, name)) => (Some(package), name),
+                None => (None, type_name),
+            };
+            // Generic instantiations are keyed by their full name, other types by their simple name
+            let key = if type_args.is_empty() {
+                simple_name
+            } else {
+                model_name.as_str()
+            };
+            if processed_models.contains(key) {
+                continue;
+            }
+
+            // Prefer the declaration from the package the reference is qualified with
+            let Some((file, spec)) = lookup_type_decl(&type_specs, package, simple_name) else {
+                debug!("No type declaration found for {}", model_name);
+                continue;
+            };
+            if spec.type_params.len() != type_args.len() {
+                debug!(
+                    "{} expects {} type arguments, got {}",
+                    spec.name,
+                    spec.type_params.len(),
+                    type_args.len()
+                );
+                continue;
+            }
+
+            debug!("Processing referenced type: {}", key);
+            let substitutions: HashMap<String, TypeExpr> = spec
+                .type_params
+                .iter()
+                .zip(&type_args)
+                .map(|(param, arg)| {
+                    let arg_type = go_ast::parse_type_expr(arg).unwrap_or_else(|_| TypeExpr::Named {
+                        package: None,
+                        name: arg.to_string(),
+                        type_args: Vec::new(),
+                    });
+                    (param.name.clone(), arg_type)
+                })
+                .collect();
+
+            let mut dependencies = HashSet::new();
+            let mut schema = match &spec.ty {
+                TypeExpr::Struct(fields) => {
+                    let (schema, field_dependencies, promoted_fields) =
+                        self.build_struct_schema(fields, &substitutions);
+                    dependencies = field_dependencies;
+                    if !promoted_fields.is_empty() {
+                        embedded_fields.insert(key.to_string(), promoted_fields);
                     }
+                    schema
+                }
+                // Named types such as `type OrderStatus string` or `type Tags []string`
+                underlying => {
+                    let mut schema = self
+                        .field_type_schema(&underlying.substitute(&substitutions), &mut dependencies);
+                    if let Some(values) = enum_values.get(simple_name) {
+                        self.apply_enum_values(values, &mut schema);
+                    }
+                    schema
+                }
+            };
+            self.apply_doc_comment(spec.doc.as_ref(), &mut schema);
+
+            processed_models.insert(key.to_string());
+            declared_at.push((key.to_string(), file.location(&spec.pos)));
+            if type_args.is_empty() {
+                // Also register package-qualified names, so references like "userModel.User"
+                // resolve as well as "User"
+                for package_alias in &package_aliases {
+                    schemas.insert(format!("{}.{}", package_alias, simple_name), schema.clone());
+                }
+            }
+            schemas.insert(key.to_string(), schema);
+
+            for dependency in dependencies {
+                if !attempted_models.contains(&dependency) && !dependency.starts_with("[]") {
+                    models_to_process.push(dependency);
                 }
             }
         }
 
-        debug!("Found referenced models: {:?}", referenced_models);
-        referenced_models
+        // Merge embedded structs into the schemas that embed them
+        self.resolve_embedded_structs(&mut schemas, &embedded_fields);
+
+        // Make sure all referenced types have schemas
+        for model_name in referenced_models {
+            if !schemas.contains_key(model_name) {
+                debug!(
+                    "No schema definition found for {}, adding a basic one",
+                    model_name
+                );
+
+                // Create a basic schema for this model
+                let basic_schema = Schema {
+                    type_: Some(serde_json::Value::String("object".to_string())),
+                    ..Default::default()
+                };
+
+                schemas.insert(model_name.clone(), basic_schema);
+                self.problems
+                    .borrow_mut()
+                    .push(ParserError::UnresolvedReference(model_name.clone()));
+            }
+        }
+
+        // Field types without a declaration are left dangling, reported at their struct
+        declared_at.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, location) in &declared_at {
+            let mut references = HashSet::new();
+            self.collect_schema_references(&schemas[key], &mut references);
+            let mut unresolved: Vec<String> = references
+                .into_iter()
+                .filter(|reference| !schemas.contains_key(reference))
+                .collect();
+            unresolved.sort();
+            for reference in unresolved {
+                self.problems.borrow_mut().push(
+                    ParserError::UnresolvedReference(format!("{} (used by {})", reference, key))
+                        .at(Some(location)),
+                );
+            }
+        }
+
+        schemas
     }
 
-    // Modified method to extract schemas only for referenced models
-    fn extract_referenced_schemas(
+    // Build the object schema for a struct body, substituting generic type parameters.
+    // Returns the schema, the types it depends on and its embedded (promoted) fields.
+    fn build_struct_schema(
         &self,
-        file_paths: &[PathBuf],
-        referenced_models: &HashSet<String>,
-    ) -> HashMap<String, Schema> {
-        use regex::Regex;
-        let mut schemas: HashMap<String, Schema> = HashMap::new();
-        let mut schema_dependencies = HashMap::new();
+        fields: &[go_ast::Field],
+        substitutions: &HashMap<String, TypeExpr>,
+    ) -> (Schema, HashSet<String>, Vec<StructField>) {
+        let mut schema = Schema {
+            type_: Some(serde_json::Value::String("object".to_string())),
+            properties: HashMap::new(),
+            ..Default::default()
+        };
 
-        // Regular expressions for struct parsing
-        let struct_regex = Regex::new(r"type\s+(\w+)\s+struct\s*\{").unwrap();
-        let field_regex =
-            Regex::new(r#"^\s*(\w+)\s+(\*?\w+(?:\.\w+)?(?:\[\])?(?:\[\])?)(?:\s+`[^`]*`)?.*$"#)
-                .unwrap();
+        let mut required_fields = Vec::new();
+        let mut field_dependencies = HashSet::new();
+        let mut promoted_fields = Vec::new();
 
-        // Track package names from imports to handle qualified model names
-        let mut package_imports: HashMap<String, String> = HashMap::new();
-        let import_regex = Regex::new(r#"import\s+\(\s*((?:[^()]*\n)*)\s*\)"#).unwrap();
-        let import_line_regex = Regex::new(r#"\s*(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
-        let single_import_regex =
-            Regex::new(r#"import\s+(?:([a-zA-Z0-9_]+)\s+)?"([^"]+)""#).unwrap();
-
-        // Track which models we need to process
-        let mut models_to_process = referenced_models.clone();
-        let mut processed_models = HashSet::new();
+        for mut field in fields.iter().flat_map(StructField::from_ast) {
+            let Some(field_name) = field.json_name() else {
+                continue;
+            };
 
-        // Add some common response types that might be referenced
-        // These are basic schema definitions for commonly referenced types
-        self.add_common_schemas(&mut schemas);
+            if !substitutions.is_empty() {
+                field.ty = field.ty.substitute(substitutions);
+            }
+            let field_type = field.type_name();
 
-        while !models_to_process.is_empty() {
-            let mut new_dependencies = HashSet::new();
-
-            for file_path in file_paths {
-                debug!("Looking for referenced models in file: {:?}", file_path);
-
-                if let Ok(content) = std::fs::read_to_string(file_path) {
-                    // Extract package imports first
-                    // Multi-line imports
-                    if let Some(caps) = import_regex.captures(&content) {
-                        if let Some(import_block) = caps.get(1) {
-                            for line in import_block.as_str().lines() {
-                                if let Some(m) = import_line_regex.captures(line) {
-                                    let alias = m.get(1).map_or_else(
-                                        || {
-                                            // If no alias, use the last part of the path
-                                            let path = m.get(2).unwrap().as_str();
-                                            path.split('/').next_back().unwrap_or(path).to_string()
-                                        },
-                                        |a| a.as_str().to_string(),
-                                    );
-                                    let path = m.get(2).unwrap().as_str().to_string();
-                                    package_imports.insert(alias, path);
-                                }
-                            }
-                        }
-                    }
+            if self.is_skipped_type(&field_type) {
+                debug!("  Skipping field {} of type {}", field_name, field_type);
+                continue;
+            }
 
-                    // Single-line imports
-                    for caps in single_import_regex.captures_iter(&content) {
-                        let alias = caps.get(1).map_or_else(
-                            || {
-                                // If no alias, use the last part of the path
-                                let path = caps.get(2).unwrap().as_str();
-                                path.split('/').next_back().unwrap_or(path).to_string()
-                            },
-                            |a| a.as_str().to_string(),
-                        );
-                        let path = caps.get(2).unwrap().as_str().to_string();
-                        package_imports.insert(alias, path);
-                    }
+            if field.is_promoted() {
+                // Embedded struct fields are merged in once all schemas are known
+                debug!("  Embedded: {}", field_type);
+                self.collect_field_dependencies(&field_type, &mut field_dependencies);
+                promoted_fields.push(field);
+                continue;
+            }
 
-                    // Now parse structs
-                    let lines: Vec<&str> = content.lines().collect();
-                    let mut i = 0;
+            debug!("  Field: {} with type: {}", field_name, field_type);
 
-                    while i < lines.len() {
-                        if let Some(captures) = struct_regex.captures(lines[i]) {
-                            let struct_name = captures.get(1).unwrap().as_str();
+            // Track dependencies in this field
+            let mut field_schema = self.field_type_schema(&field.ty, &mut field_dependencies);
+            self.apply_validation_rules(&field.validation_rules(), &mut field_schema);
+            // A nil pointer is encoded as null, unless omitempty leaves the field out
+            if matches!(field.ty, TypeExpr::Pointer(_)) && !field.is_omitempty() {
+                make_nullable(&mut field_schema);
+            }
+            self.apply_doc_comment(field.doc.as_ref(), &mut field_schema);
+            field_schema.extensions.extend(field.extensions());
 
-                            // Check if this struct is one we need to process
-                            // Look for both simple names and qualified names
-                            let is_referenced = models_to_process.contains(struct_name)
-                                || models_to_process.iter().any(|m| {
-                                    m.contains('.')
-                                        && m.split('.').next_back().unwrap_or("") == struct_name
-                                });
+            // Only an explicit `required` rule makes the field required
+            if field.has_required_rule() {
+                required_fields.push(field_name.clone());
+            }
 
-                            if is_referenced && !processed_models.contains(struct_name) {
-                                debug!("Processing referenced struct: {}", struct_name);
-                                processed_models.insert(struct_name.to_string());
+            schema.properties.insert(field_name, Box::new(field_schema));
+        }
 
-                                let mut schema = Schema {
-                                    type_: Some(serde_json::Value::String("object".to_string())),
-                                    properties: HashMap::new(),
-                                    ..Default::default()
-                                };
+        // Add required fields if any
+        if !required_fields.is_empty() {
+            schema.required = Some(required_fields);
+        }
+
+        (schema, field_dependencies, promoted_fields)
+    }
+
+    // Schema for a field type, building anonymous structs inline and adding the
+    // named types it refers to to `dependencies`
+    fn field_type_schema(&self, ty: &TypeExpr, dependencies: &mut HashSet<String>) -> Schema {
+        match ty {
+            TypeExpr::Struct(fields) => {
+                let (schema, field_dependencies, promoted_fields) =
+                    self.build_struct_schema(fields, &HashMap::new());
+                if !promoted_fields.is_empty() {
+                    debug!("  Ignoring embedded fields of an anonymous struct");
+                }
+                dependencies.extend(field_dependencies);
+                schema
+            }
+            TypeExpr::Pointer(inner) if inner.has_inline_struct() => {
+                self.field_type_schema(inner, dependencies)
+            }
+            TypeExpr::Slice(elem) if elem.has_inline_struct() => Schema {
+                type_: Some(serde_json::Value::String("array".to_string())),
+                items: Some(Box::new(self.field_type_schema(elem, dependencies))),
+                ..Default::default()
+            },
+            TypeExpr::Map { value, .. } if value.has_inline_struct() => Schema {
+                type_: Some(serde_json::Value::String("object".to_string())),
+                additionalProperties: serde_json::to_value(self.field_type_schema(value, dependencies))
+                    .ok(),
+                ..Default::default()
+            },
+            // Fixed-size arrays are arrays with a known length; unlike byte slices,
+            // encoding/json writes byte arrays as arrays of numbers too
+            TypeExpr::Array { len, elem } => {
+                let length = len.parse::<u64>().ok();
+                Schema {
+                    type_: Some(serde_json::Value::String("array".to_string())),
+                    items: Some(Box::new(self.field_type_schema(elem, dependencies))),
+                    minItems: length,
+                    maxItems: length,
+                    ..Default::default()
+                }
+            }
+            // Functions and channels cannot be encoded as JSON
+            TypeExpr::Func | TypeExpr::Chan(_) => Schema::default(),
+            _ => {
+                let type_name = ty.to_string();
+                self.collect_field_dependencies(&type_name, dependencies);
+                self.convert_go_type_to_schema(&type_name)
+            }
+        }
+    }
 
-                                let mut required_fields = Vec::new();
-                                let mut field_dependencies = HashSet::new();
-                                let mut j = i + 1;
+    // Turn the constants of a named primitive type into the enum of its schema
+    fn apply_enum_values(&self, values: &[EnumValue], schema: &mut Schema) {
+        if values.is_empty() || schema.ref_.is_some() {
+            return;
+        }
 
-                                // Parse fields until we reach the closing brace
-                                while j < lines.len() && !lines[j].trim().starts_with('}') {
-                                    if let Some(field_captures) = field_regex.captures(lines[j]) {
-                                        let field_name = field_captures.get(1).unwrap().as_str();
-                                        let field_type = field_captures.get(2).unwrap().as_str();
+        schema.enum_values = Some(values.iter().map(|v| v.value.clone()).collect());
+        schema.extensions.insert(
+            "x-enum-varnames".to_string(),
+            serde_json::json!(values.iter().map(|v| &v.name).collect::<Vec<_>>()),
+        );
 
-                                        debug!("  Field: {} with type: {}", field_name, field_type);
+        // Trailing comments on the constants describe the individual values
+        if values.iter().any(|v| v.comment.is_some()) {
+            let descriptions: Vec<&str> = values
+                .iter()
+                .map(|v| v.comment.as_deref().unwrap_or(""))
+                .collect();
+            let comments: serde_json::Map<String, serde_json::Value> = values
+                .iter()
+                .filter_map(|v| Some((v.name.clone(), serde_json::json!(v.comment.as_ref()?))))
+                .collect();
+            schema
+                .extensions
+                .insert("x-enum-descriptions".to_string(), serde_json::json!(descriptions));
+            schema
+                .extensions
+                .insert("x-enum-comments".to_string(), serde_json::Value::Object(comments));
+        }
+    }
 
-                                        // Track dependencies in this field
-                                        let field_schema =
-                                            self.convert_go_type_to_schema(field_type);
+    // Collect typed constants from `const` declarations, keyed by their type name
+    fn collect_enum_values(&self, files: &[SourceFile]) -> HashMap<String, Vec<EnumValue>> {
+        let mut enum_values: HashMap<String, Vec<EnumValue>> = HashMap::new();
 
-                                        // Add any referenced types to our dependencies
-                                        self.collect_field_dependencies(
-                                            field_type,
-                                            &mut field_dependencies,
-                                        );
+        for decl in files.iter().flat_map(|file| &file.ast.consts) {
+            // Specs without a value repeat the previous type and expressions,
+            // as Go does for implicit repetition with `iota`
+            let mut current_type: Option<&TypeExpr> = None;
+            let mut current_values: &[String] = &[];
 
-                                        // Check if the field is required (not a pointer type)
-                                        if !field_type.starts_with("*") {
-                                            required_fields.push(field_name.to_string());
-                                        }
+            for (iota, spec) in decl.specs.iter().enumerate() {
+                if !spec.values.is_empty() {
+                    current_type = spec.ty.as_ref();
+                    current_values = &spec.values;
+                }
 
-                                        schema
-                                            .properties
-                                            .insert(field_name.to_string(), Box::new(field_schema));
-                                    }
-                                    j += 1;
-                                }
+                // Enums are keyed by the unqualified type name
+                let Some(TypeExpr::Named { name: type_name, .. }) = current_type else {
+                    continue;
+                };
+                let comment = spec
+                    .comment
+                    .as_ref()
+                    .map(|comment| comment.text())
+                    .filter(|comment| !comment.is_empty());
+
+                for (name, expr) in spec.names.iter().zip(current_values) {
+                    if name == "_" {
+                        continue;
+                    }
+                    match self.eval_const_expr(expr, iota as i64) {
+                        Some(value) => enum_values
+                            .entry(type_name.clone())
+                            .or_default()
+                            .push(EnumValue {
+                                name: name.clone(),
+                                value,
+                                comment: comment.clone(),
+                            }),
+                        None => debug!("Unsupported constant expression for {}: {}", name, expr),
+                    }
+                }
+            }
+        }
 
-                                // Add required fields if any
-                                if !required_fields.is_empty() {
-                                    schema.required = Some(required_fields);
-                                }
+        enum_values
+    }
 
-                                // Add the schema with different names for better reference resolution
+    // Evaluate the constant expressions used for enums: literals, `iota`, `iota + 1`, `1 << iota`
+    fn eval_const_expr(&self, expr: &str, iota: i64) -> Option<serde_json::Value> {
+        let expr = expr.trim();
 
-                                // 1. Add with the simple name (e.g., "User")
-                                schemas.insert(struct_name.to_string(), schema.clone());
+        if expr.starts_with('"') {
+            return serde_json::from_str::<String>(expr).ok().map(serde_json::Value::from);
+        }
+        if let Some(raw) = expr.strip_prefix('`').and_then(|e| e.strip_suffix('`')) {
+            return Some(serde_json::Value::from(raw));
+        }
+        if expr == "true" || expr == "false" {
+            return Some(serde_json::Value::Bool(expr == "true"));
+        }
 
-                                // 2. Also add package-qualified names for all known package imports
-                                // This handles references like "userModel.User" by creating schemas
-                                // with both names "User" and "userModel.User"
-                                for package_alias in package_imports.keys() {
-                                    let qualified_name =
-                                        format!("{}.{}", package_alias, struct_name);
-                                    debug!("Adding schema with qualified name: {}", qualified_name);
-                                    schemas.insert(qualified_name, schema.clone());
-                                }
+        self.eval_integer_expr(&expr.replace("iota", &iota.to_string()))
+            .map(serde_json::Value::from)
+            .or_else(|| expr.parse::<f64>().ok().map(serde_json::Value::from))
+    }
 
-                                // Store dependencies for this model
-                                schema_dependencies
-                                    .insert(struct_name.to_string(), field_dependencies);
+    // Evaluate a small integer expression with a single +, -, * or << operator
+    fn eval_integer_expr(&self, expr: &str) -> Option<i64> {
+        let expr = expr.trim();
+        let expr = expr
+            .strip_prefix('(')
+            .and_then(|e| e.strip_suffix(')'))
+            .unwrap_or(expr);
 
-                                i = j;
-                            }
-                        }
-                        i += 1;
+        if let Ok(value) = expr.parse::<i64>() {
+            return Some(value);
+        }
+
+        // Skip a leading sign so negative literals are not split
+        let tail = expr.get(1..)?;
+        for operator in ["<<", "+", "-", "*"] {
+            if let Some(index) = tail.find(operator).map(|i| i + 1) {
+                let left = self.eval_integer_expr(&expr[..index])?;
+                let right = self.eval_integer_expr(&expr[index + operator.len()..])?;
+                return match operator {
+                    "<<" => left.checked_shl(u32::try_from(right).ok()?),
+                    "+" => left.checked_add(right),
+                    "-" => left.checked_sub(right),
+                    _ => left.checked_mul(right),
+                };
+            }
+        }
+
+        None
+    }
+
+    // Split `pkg.Page[models.User]` into its base type and type arguments
+    fn split_generic_instantiation<'a>(&self, type_name: &'a str) -> Option<(&'a str, Vec<&'a str>)> {
+        let open = type_name.find('[')?;
+        let base = &type_name[..open];
+        if base.is_empty() || base == "map" || !type_name.ends_with(']') {
+            return None;
+        }
+
+        let args = &type_name[open + 1..type_name.len() - 1];
+        Some((base, self.split_outside_brackets(args, |c| c == ',')))
+    }
+
+    // Split `map[string][]models.Item` into its key and value types
+    fn split_map_type<'a>(&self, type_name: &'a str) -> Option<(&'a str, &'a str)> {
+        let rest = type_name.strip_prefix("map[")?;
+
+        // The key type may itself contain brackets, e.g. map[[2]int]string
+        let mut depth = 1usize;
+        for (index, c) in rest.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let value_type = &rest[index + 1..];
+                        return (!value_type.is_empty()).then_some((&rest[..index], value_type));
                     }
                 }
+                _ => {}
             }
+        }
 
-            // Add all new dependencies to process
-            models_to_process.clear();
-            for deps in schema_dependencies.values() {
-                for dep in deps {
-                    if !processed_models.contains(dep) && !dep.contains("[]") {
-                        models_to_process.insert(dep.clone());
-                        new_dependencies.insert(dep.clone());
-                    }
+        None
+    }
+
+    // Readable component name for a generic instantiation, following swag's convention:
+    // `response.Page[models.User]` becomes `response.Page-models_User`
+    fn generic_schema_name(&self, type_name: &str) -> String {
+        let Some((base, args)) = self.split_generic_instantiation(type_name) else {
+            return type_name.to_string();
+        };
+
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                let arg: String = arg.chars().filter(|c| !c.is_whitespace()).collect();
+                self.generic_schema_name(&arg)
+                    .replace("[]", "array_")
+                    .replace(['[', ']'], "_")
+                    .replace('*', "")
+                    .replace('.', "_")
+            })
+            .collect();
+
+        format!("{}-{}", base, args.join("-"))
+    }
+
+    // Rename generic instantiation schemas and every `$ref` pointing at them
+    fn rename_generic_schemas(
+        &self,
+        operations: &mut [ParsedOperation],
+        schemas: &mut HashMap<String, Schema>,
+    ) {
+        let renames: HashMap<String, String> = schemas
+            .keys()
+            .filter(|name| self.split_generic_instantiation(name).is_some())
+            .map(|name| (name.clone(), self.generic_schema_name(name)))
+            .collect();
+        if renames.is_empty() {
+            return;
+        }
+
+        for (old_name, new_name) in &renames {
+            if let Some(schema) = schemas.remove(old_name) {
+                schemas.insert(new_name.clone(), schema);
+            }
+        }
+
+        let mut rename_ref = |schema: &mut Schema| {
+            if let Some(ref_) = &mut schema.ref_ {
+                let new_name = ref_
+                    .strip_prefix("#/components/schemas/")
+                    .and_then(|name| renames.get(name));
+                if let Some(new_name) = new_name {
+                    *ref_ = format!("#/components/schemas/{}", new_name);
                 }
             }
+        };
+        for schema in schemas.values_mut() {
+            schema.walk_mut(&mut rename_ref);
+        }
+        for operation in operations {
+            operation.operation.walk_schemas_mut(&mut rename_ref);
+        }
+    }
 
-            // If no new dependencies were found, we're done
-            if new_dependencies.is_empty() {
-                break;
+    // Replace every schema of a struct with embedded fields by its flattened or allOf form
+    fn resolve_embedded_structs(
+        &self,
+        schemas: &mut HashMap<String, Schema>,
+        embedded_fields: &HashMap<String, Vec<StructField>>,
+    ) {
+        let mut resolved = HashMap::new();
+        for struct_name in embedded_fields.keys() {
+            let schema = if self.embedded_all_of {
+                self.compose_embedded_schema(struct_name, schemas, embedded_fields)
+            } else {
+                self.flatten_embedded_schema(
+                    struct_name,
+                    schemas,
+                    embedded_fields,
+                    &mut HashSet::new(),
+                )
+            };
+            if let Some(schema) = schema {
+                resolved.insert(struct_name.clone(), schema);
             }
+        }
 
-            debug!(
-                "Added {} new dependencies to process",
-                new_dependencies.len()
-            );
+        // Structs are registered under their simple and package-qualified names
+        for (struct_name, schema) in resolved {
+            let qualified_suffix = format!(".{}", struct_name);
+            for (name, existing) in schemas.iter_mut() {
+                if *name == struct_name || name.ends_with(&qualified_suffix) {
+                    *existing = schema.clone();
+                }
+            }
         }
+    }
 
-        // Make sure all referenced types have schemas
-        for model_name in referenced_models {
-            if !schemas.contains_key(model_name) {
-                debug!(
-                    "No schema definition found for {}, adding a basic one",
-                    model_name
-                );
+    // Promote the properties of embedded structs into the parent, recursing through nested embeds
+    fn flatten_embedded_schema(
+        &self,
+        struct_name: &str,
+        schemas: &HashMap<String, Schema>,
+        embedded_fields: &HashMap<String, Vec<StructField>>,
+        visiting: &mut HashSet<String>,
+    ) -> Option<Schema> {
+        let mut schema = schemas.get(struct_name)?.clone();
+        if !visiting.insert(struct_name.to_string()) {
+            return Some(schema);
+        }
 
-                // Create a basic schema for this model
-                let basic_schema = Schema {
-                    type_: Some(serde_json::Value::String("object".to_string())),
-                    ..Default::default()
-                };
+        let mut required_fields = schema.required.take().unwrap_or_default();
+        for field in embedded_fields.get(struct_name).into_iter().flatten() {
+            let field_type = field.type_name();
+            let type_name = field_type.trim_start_matches('*');
+            let embedded_schema = if embedded_fields.contains_key(&field.name) {
+                self.flatten_embedded_schema(&field.name, schemas, embedded_fields, visiting)
+            } else {
+                schemas
+                    .get(type_name)
+                    .or_else(|| schemas.get(&field.name))
+                    .cloned()
+            };
+            let Some(embedded_schema) = embedded_schema else {
+                debug!("No schema found for embedded type {}", type_name);
+                continue;
+            };
 
-                schemas.insert(model_name.clone(), basic_schema);
+            let embedded_required = embedded_schema.required.unwrap_or_default();
+            for (property_name, property) in embedded_schema.properties {
+                // Fields declared on the outer struct shadow promoted ones
+                if schema.properties.contains_key(&property_name) {
+                    continue;
+                }
+                // A nil embedded pointer omits all of its fields
+                if !matches!(field.ty, TypeExpr::Pointer(_)) && embedded_required.contains(&property_name)
+                {
+                    required_fields.push(property_name.clone());
+                }
+                schema.properties.insert(property_name, property);
             }
         }
 
-        schemas
+        visiting.remove(struct_name);
+        if !required_fields.is_empty() {
+            schema.required = Some(required_fields);
+        }
+        Some(schema)
+    }
+
+    // Build `allOf: [$ref embedded..., own properties]` for a struct with embedded fields
+    fn compose_embedded_schema(
+        &self,
+        struct_name: &str,
+        schemas: &HashMap<String, Schema>,
+        embedded_fields: &HashMap<String, Vec<StructField>>,
+    ) -> Option<Schema> {
+        let mut own_schema = schemas.get(struct_name)?.clone();
+        let description = own_schema.description.take();
+        let deprecated = own_schema.deprecated.take();
+        let mut all_of: Vec<Schema> = embedded_fields
+            .get(struct_name)
+            .into_iter()
+            .flatten()
+            .map(|field| Schema {
+                ref_: Some(format!(
+                    "#/components/schemas/{}",
+                    field.type_name().trim_start_matches('*')
+                )),
+                ..Default::default()
+            })
+            .collect();
+        if !own_schema.properties.is_empty() {
+            all_of.push(own_schema);
+        }
+
+        Some(Schema {
+            allOf: Some(all_of),
+            description,
+            deprecated,
+            ..Default::default()
+        })
     }
 
     // Add common schema definitions that are often referenced in Go APIs
@@ -3466,15 +5137,79 @@ impl GoParser {
         );
     }
 
+    // Schema for types with a fixed JSON representation, from the overrides file or
+    // the built-in table (time.Time, uuid.UUID, []byte, ...)
+    fn well_known_type_schema(&self, type_name: &str) -> Option<Schema> {
+        match self.type_overrides.get(type_name) {
+            Some(TypeOverride::Replace { target, format }) => {
+                // Targets may be Go types or OpenAPI primitives such as `number`
+                let mut schema = self.parse_type_expression(target);
+                if format.is_some() {
+                    schema.format = format.clone();
+                }
+                return Some(schema);
+            }
+            // Skipped types are dropped from structs; anything else sees any JSON value
+            Some(TypeOverride::Skip) => return Some(Schema::default()),
+            None => {}
+        }
+
+        let primitive = |type_: &str, format: Option<&str>| Schema {
+            type_: Some(serde_json::Value::String(type_.to_string())),
+            format: format.map(str::to_string),
+            ..Default::default()
+        };
+        // database/sql null types carry an explicit null when not valid
+        let nullable = |type_: &str, format: Option<&str>| Schema {
+            type_: Some(serde_json::json!([type_, "null"])),
+            format: format.map(str::to_string),
+            ..Default::default()
+        };
+
+        let schema = match type_name {
+            "time.Time" => primitive("string", Some("date-time")),
+            "time.Duration" => primitive("integer", Some("int64")),
+            "uuid.UUID" => primitive("string", Some("uuid")),
+            "decimal.Decimal" => primitive("string", Some("decimal")),
+            "json.RawMessage" => Schema::default(),
+            "[]byte" | "[]uint8" => primitive("string", Some("byte")),
+            // Either an IPv4 or an IPv6 address, which no single format covers
+            "net.IP" | "netip.Addr" => primitive("string", None),
+            "url.URL" => primitive("string", Some("uri")),
+            "big.Int" => primitive("integer", None),
+            "sql.NullString" => nullable("string", None),
+            "sql.NullInt16" | "sql.NullInt32" | "sql.NullByte" => nullable("integer", None),
+            "sql.NullInt64" => nullable("integer", Some("int64")),
+            "sql.NullFloat64" => nullable("number", None),
+            "sql.NullBool" => nullable("boolean", None),
+            "sql.NullTime" => nullable("string", Some("date-time")),
+            _ => return None,
+        };
+
+        Some(schema)
+    }
+
+    // Whether the overrides file asks for fields of this type to be left out
+    fn is_skipped_type(&self, type_name: &str) -> bool {
+        matches!(
+            self.type_overrides.get(type_name.trim_start_matches('*')),
+            Some(TypeOverride::Skip)
+        )
+    }
+
     // Helper to convert Go types to OpenAPI schema
     fn convert_go_type_to_schema(&self, field_type: &str) -> Schema {
+        if let Some(schema) = self.well_known_type_schema(field_type) {
+            return schema;
+        }
+
         match field_type {
             "string" => Schema {
                 type_: Some(serde_json::Value::String("string".to_string())),
                 ..Default::default()
             },
             "int" | "int8" | "int16" | "int32" | "int64" | "uint" | "uint8" | "uint16"
-            | "uint32" | "uint64" => Schema {
+            | "uint32" | "uint64" | "byte" | "rune" => Schema {
                 type_: Some(serde_json::Value::String("integer".to_string())),
                 ..Default::default()
             },
@@ -3486,46 +5221,29 @@ impl GoParser {
                 type_: Some(serde_json::Value::String("boolean".to_string())),
                 ..Default::default()
             },
+            "interface{}" | "interface" | "any" => {
+                // Any JSON value
+                Schema::default()
+            }
             t if t.starts_with("[]") => {
-                // Array type
+                // Array type, e.g. []string or []packageName.Type
                 let item_type = &t[2..]; // Remove "[]" prefix
 
-                // Handle qualified references in arrays, e.g. []packageName.Type
-                if item_type.contains('.') {
-                    let parts: Vec<&str> = item_type.split('.').collect();
-                    if parts.len() == 2 {
-                        let package_alias = parts[0];
-                        let type_name = parts[1];
-
-                        // Full qualified name for the reference
-                        let ref_name = format!("{}.{}", package_alias, type_name);
-
-                        Schema {
-                            type_: Some(serde_json::Value::String("array".to_string())),
-                            items: Some(Box::new(Schema {
-                                ref_: Some(format!("#/components/schemas/{}", ref_name)),
-                                ..Default::default()
-                            })),
-                            ..Default::default()
-                        }
-                    } else {
-                        Schema {
-                            type_: Some(serde_json::Value::String("array".to_string())),
-                            items: Some(Box::new(Schema {
-                                ref_: Some(format!("#/components/schemas/{}", item_type)),
-                                ..Default::default()
-                            })),
-                            ..Default::default()
-                        }
-                    }
-                } else {
-                    let item_schema = self.convert_go_type_to_schema(item_type);
+                Schema {
+                    type_: Some(serde_json::Value::String("array".to_string())),
+                    items: Some(Box::new(self.convert_go_type_to_schema(item_type))),
+                    ..Default::default()
+                }
+            }
+            t if self.split_map_type(t).is_some() => {
+                // Map type: JSON objects keyed by strings, values described by additionalProperties
+                let (_, value_type) = self.split_map_type(t).unwrap();
+                let value_schema = self.convert_go_type_to_schema(value_type);
 
-                    Schema {
-                        type_: Some(serde_json::Value::String("array".to_string())),
-                        items: Some(Box::new(item_schema)),
-                        ..Default::default()
-                    }
+                Schema {
+                    type_: Some(serde_json::Value::String("object".to_string())),
+                    additionalProperties: serde_json::to_value(value_schema).ok(),
+                    ..Default::default()
                 }
             }
             t if t.starts_with("*") => {
@@ -3533,6 +5251,13 @@ impl GoParser {
                 let base_type = &t[1..]; // Remove "*" prefix
                 self.convert_go_type_to_schema(base_type)
             }
+            t if self.split_generic_instantiation(t).is_some() => {
+                // Generic instantiation (e.g. Page[models.User]), renamed once all schemas are built
+                Schema {
+                    ref_: Some(format!("#/components/schemas/{}", t)),
+                    ..Default::default()
+                }
+            }
             t if t.contains('.') => {
                 // This is a reference to a type in another package (e.g., user.User)
                 let parts: Vec<&str> = t.split('.').collect();
@@ -3568,19 +5293,32 @@ impl GoParser {
     fn collect_field_dependencies(&self, field_type: &str, dependencies: &mut HashSet<String>) {
         match field_type {
             "string" | "int" | "int8" | "int16" | "int32" | "int64" | "uint" | "uint8"
-            | "uint16" | "uint32" | "uint64" | "float32" | "float64" | "bool" => {
+            | "uint16" | "uint32" | "uint64" | "float32" | "float64" | "bool" | "byte"
+            | "rune" | "interface{}" | "interface" | "any" => {
                 // Basic types have no dependencies
             }
+            t if self.well_known_type_schema(t).is_some() => {
+                // Mapped to primitives, nothing to extract
+            }
             t if t.starts_with("[]") => {
                 // Array type - collect dependencies from item type
                 let item_type = &t[2..]; // Remove "[]" prefix
                 self.collect_field_dependencies(item_type, dependencies);
             }
+            t if self.split_map_type(t).is_some() => {
+                // Map type - collect dependencies from the value type
+                let (_, value_type) = self.split_map_type(t).unwrap();
+                self.collect_field_dependencies(value_type, dependencies);
+            }
             t if t.starts_with("*") => {
                 // Pointer type - collect dependencies from base type
                 let base_type = &t[1..]; // Remove "*" prefix
                 self.collect_field_dependencies(base_type, dependencies);
             }
+            t if self.split_generic_instantiation(t).is_some() => {
+                // Generic instantiation - its type arguments are resolved when it is built
+                dependencies.insert(t.to_string());
+            }
             t if t.contains('.') => {
                 // Reference to a type in another package
                 let parts: Vec<&str> = t.split('.').collect();